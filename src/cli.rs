@@ -1,10 +1,13 @@
 use crate::parry::bounding_volume::Aabb;
 use bevy::prelude::*;
-use clap::Parser;
+use clap::builder::RangedU64ValueParser;
+use clap::{ArgGroup, Parser};
 use nalgebra::point;
+use std::path::PathBuf;
 
-#[derive(Parser, Debug, Copy, Clone, Resource)]
+#[derive(Parser, Debug, Clone, Resource)]
 #[command(author, version, about, long_about = None)]
+#[command(group(ArgGroup::new("stop").args(["steps", "until"]).multiple(true)))]
 pub struct CliArgs {
     #[arg(long, default_value_t = -100_000.0)]
     xmin: f32,
@@ -22,6 +25,24 @@ pub struct CliArgs {
     pub distributed_physics: bool,
     #[arg(long, default_value_t = false)]
     pub lower_graphics: bool,
+    /// Run the simulation without any window, and write the results to `--output`.
+    ///
+    /// The simulation stops after `--steps` or `--until`, at least one of them is required.
+    #[arg(long, default_value_t = false, requires_all = ["scene", "output", "stop"])]
+    pub headless: bool,
+    /// The scene file (JSON) loaded at startup.
+    #[arg(long)]
+    pub scene: Option<PathBuf>,
+    /// The number of steps simulated in headless mode.
+    #[arg(long, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub steps: Option<usize>,
+    /// The simulated time (in seconds) after which the headless simulation stops.
+    #[arg(long, value_parser = parse_positive_time)]
+    pub until: Option<f32>,
+    /// The file where the body trajectories and timings are written in headless mode,
+    /// as CSV or JSON depending on its extension.
+    #[arg(long)]
+    pub output: Option<PathBuf>,
 }
 
 impl CliArgs {
@@ -47,3 +68,13 @@ impl CliArgs {
         result
     }
 }
+
+/// Parses the `--until` time, which must be positive for the simulation to run at least one
+/// step.
+fn parse_positive_time(arg: &str) -> Result<f32, String> {
+    match arg.parse::<f32>() {
+        Ok(time) if time > 0.0 => Ok(time),
+        Ok(_) => Err("the time must be positive".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
@@ -0,0 +1,84 @@
+use crate::render::RenderSystems;
+use bevy::prelude::*;
+use bevy_rapier::geometry::Collider;
+use bevy_rapier::math::Vect;
+
+mod mouse;
+
+#[derive(Default, Clone, Resource)]
+pub struct CutState {
+    /// The point where the cutting line started.
+    #[cfg(feature = "dim2")]
+    pub start: Option<Vect>,
+    /// The mouse ray where the cutting line started.
+    #[cfg(feature = "dim3")]
+    pub start: Option<(Vect, Vect)>,
+}
+
+pub struct CutPlugin;
+
+impl Plugin for CutPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(CutState::default())
+            .add_systems(
+                Update,
+                mouse::handle_cut_click.in_set(RenderSystems::BeforeCommands),
+            )
+            .add_systems(Update, mouse::handle_cut_hover);
+    }
+}
+
+/// The endpoints of the cutting line, in world-space.
+#[cfg(feature = "dim2")]
+fn cutting_line(start: Vect, end: Vect) -> [Vect; 2] {
+    [start, end]
+}
+
+/// The endpoints of the cutting line, in world-space.
+///
+/// They are taken slightly in front of the camera, along each mouse ray, so the line
+/// matches what was drawn on the screen.
+#[cfg(feature = "dim3")]
+fn cutting_line(start: (Vect, Vect), end: (Vect, Vect)) -> [Vect; 2] {
+    const DEPTH: f32 = 1.0;
+    [
+        start.0 + start.1.normalize_or_zero() * DEPTH,
+        end.0 + end.1.normalize_or_zero() * DEPTH,
+    ]
+}
+
+/// The part of the cutting plane swept by the cutting line, which selects the bodies to cut.
+#[cfg(feature = "dim2")]
+fn cutting_blade(start: Vect, end: Vect) -> Vec<Collider> {
+    vec![Collider::segment(start, end)]
+}
+
+/// The part of the cutting plane swept by the cutting line, which selects the bodies to cut.
+///
+/// This is the quad between the mouse rays at both ends of the line, split in two triangles.
+#[cfg(feature = "dim3")]
+fn cutting_blade(start: (Vect, Vect), end: (Vect, Vect)) -> Vec<Collider> {
+    const LENGTH: f32 = 1.0e3;
+    let [a, b] = cutting_line(start, end);
+    let far_a = a + start.1.normalize_or_zero() * LENGTH;
+    let far_b = b + end.1.normalize_or_zero() * LENGTH;
+    vec![
+        Collider::triangle(a, b, far_b),
+        Collider::triangle(a, far_b, far_a),
+    ]
+}
+
+/// The cutting plane, as a point and a normal, swept by the cutting line.
+#[cfg(feature = "dim2")]
+fn cutting_plane(start: Vect, end: Vect) -> Option<(Vect, Vect)> {
+    let normal = (end - start).perp().try_normalize()?;
+    Some((start, normal))
+}
+
+/// The cutting plane, as a point and a normal, swept by the cutting line.
+#[cfg(feature = "dim3")]
+fn cutting_plane(start: (Vect, Vect), end: (Vect, Vect)) -> Option<(Vect, Vect)> {
+    let [a, b] = cutting_line(start, end);
+    let normal = (b - a).cross(start.1).try_normalize()?;
+    Some((a, normal))
+}
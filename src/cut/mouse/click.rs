@@ -0,0 +1,59 @@
+use crate::cut::CutState;
+use crate::operation::{Operation, Operations};
+use crate::selection::SceneMouse;
+use crate::ui::{ActiveMouseAction, SelectedTool, UiState};
+use bevy::prelude::*;
+
+pub fn handle_cut_click(
+    mut cut_state: ResMut<CutState>,
+    mut mouse_action: ResMut<ActiveMouseAction>,
+    mut operations: ResMut<Operations>,
+    ui_state: Res<UiState>,
+    scene_mouse: Res<SceneMouse>,
+    mouse: Res<ButtonInput<MouseButton>>,
+) {
+    let mut reset = false;
+
+    match ui_state.selected_tool {
+        SelectedTool::Cut => {}
+        _ => {
+            reset = true;
+        }
+    }
+
+    if *mouse_action != ActiveMouseAction::Cut && *mouse_action != ActiveMouseAction::None {
+        reset = true;
+    }
+
+    #[cfg(feature = "dim2")]
+    let cursor = scene_mouse.point;
+    #[cfg(feature = "dim3")]
+    let cursor = scene_mouse.ray;
+
+    if !reset {
+        if mouse.just_pressed(MouseButton::Left) && cursor.is_some() {
+            cut_state.start = cursor;
+            *mouse_action = ActiveMouseAction::Cut;
+        }
+
+        if mouse.just_released(MouseButton::Left) {
+            if let (Some(start), Some(end)) = (cut_state.start, cursor) {
+                if let Some((point, normal)) = crate::cut::cutting_plane(start, end) {
+                    operations.push(Operation::Cut {
+                        point,
+                        normal,
+                        blade: crate::cut::cutting_blade(start, end),
+                    });
+                }
+            }
+            reset = true;
+        }
+    }
+
+    if reset {
+        cut_state.start = None;
+        if *mouse_action == ActiveMouseAction::Cut {
+            *mouse_action = ActiveMouseAction::None;
+        }
+    }
+}
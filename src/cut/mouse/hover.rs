@@ -0,0 +1,25 @@
+use crate::cut::CutState;
+use crate::selection::SceneMouse;
+use crate::styling::Theme;
+use bevy::prelude::*;
+
+/// Draws the line being dragged with the cut tool.
+pub fn handle_cut_hover(
+    mut gizmos: Gizmos,
+    cut_state: Res<CutState>,
+    scene_mouse: Res<SceneMouse>,
+    theme: Res<Theme>,
+) {
+    #[cfg(feature = "dim2")]
+    let cursor = scene_mouse.point;
+    #[cfg(feature = "dim3")]
+    let cursor = scene_mouse.ray;
+
+    if let (Some(start), Some(end)) = (cut_state.start, cursor) {
+        let [a, b] = crate::cut::cutting_line(start, end);
+        #[cfg(feature = "dim2")]
+        gizmos.line_2d(a, b, theme.insertion_preview_color());
+        #[cfg(feature = "dim3")]
+        gizmos.line(a, b, theme.insertion_preview_color());
+    }
+}
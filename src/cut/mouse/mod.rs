@@ -0,0 +1,5 @@
+pub use self::click::*;
+pub use self::hover::*;
+
+mod click;
+mod hover;
@@ -0,0 +1,364 @@
+//! Batch simulation without any window or rendering, for regression testing.
+
+use crate::cli::CliArgs;
+use crate::operation::{Operation, Operations};
+use crate::PhysicsProgress;
+use bevy::app::{AppExit, ScheduleRunnerPlugin};
+use bevy::prelude::*;
+use bevy::render::settings::{RenderCreation, WgpuSettings};
+use bevy::render::RenderPlugin;
+use bevy::winit::WinitPlugin;
+use bevy_rapier::prelude::*;
+use bevy_rapier::rapier::counters::Counters;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::time::Duration;
+
+/// The state of a body after a simulation step.
+struct BodyRecord {
+    entity: Entity,
+    name: Option<String>,
+    translation: Vect,
+    #[cfg(feature = "dim2")]
+    rotation: f32,
+    #[cfg(feature = "dim3")]
+    rotation: Quat,
+    linvel: Vect,
+    #[cfg(feature = "dim2")]
+    angvel: f32,
+    #[cfg(feature = "dim3")]
+    angvel: Vect,
+}
+
+/// The timings (in milliseconds) of a simulation step, taken from the pipeline `Counters`.
+struct StepTimings {
+    step: f64,
+    collision_detection: f64,
+    broad_phase: f64,
+    narrow_phase: f64,
+    island_construction: f64,
+    solver: f64,
+    ccd: f64,
+}
+
+impl StepTimings {
+    const NAMES: [&'static str; 7] = [
+        "step",
+        "collision_detection",
+        "broad_phase",
+        "narrow_phase",
+        "island_construction",
+        "solver",
+        "ccd",
+    ];
+
+    fn new(counters: &Counters) -> Self {
+        Self {
+            step: counters.step_time(),
+            collision_detection: counters.collision_detection_time(),
+            broad_phase: counters.broad_phase_time(),
+            narrow_phase: counters.narrow_phase_time(),
+            island_construction: counters.island_construction_time(),
+            solver: counters.solver_time(),
+            ccd: counters.ccd_time(),
+        }
+    }
+
+    fn values(&self) -> [f64; 7] {
+        [
+            self.step,
+            self.collision_detection,
+            self.broad_phase,
+            self.narrow_phase,
+            self.island_construction,
+            self.solver,
+            self.ccd,
+        ]
+    }
+}
+
+struct StepRecord {
+    step: usize,
+    time: Real,
+    timings: StepTimings,
+    bodies: Vec<BodyRecord>,
+}
+
+#[derive(Resource, Default)]
+struct HeadlessRecording {
+    steps: Vec<StepRecord>,
+}
+
+/// Runs the simulation described by the command-line arguments, without opening any
+/// window, and writes the recorded trajectories and timings to the output file.
+///
+/// The process exits with a non-zero status if the scene can’t be loaded or the output
+/// can’t be written.
+pub fn run(args: CliArgs) {
+    let mut app = App::new();
+    app.insert_resource(args)
+        .insert_resource(PhysicsProgress::default())
+        .insert_resource(HeadlessRecording::default())
+        .add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: bevy::window::ExitCondition::DontExit,
+                    ..default()
+                })
+                .set(RenderPlugin {
+                    render_creation: RenderCreation::Automatic(WgpuSettings {
+                        backends: None,
+                        ..default()
+                    }),
+                    ..default()
+                })
+                .disable::<WinitPlugin>(),
+        )
+        .add_plugins(ScheduleRunnerPlugin::run_loop(Duration::ZERO))
+        .add_plugins(RapierPhysicsPlugin::<NoUserData>::default())
+        .insert_resource(crate::styling::ColorGenerator::default())
+        .add_plugins(crate::operation::RapierOperationsPlugin)
+        .add_systems(Startup, crate::init_profiling_and_gravity)
+        .add_systems(Startup, (setup_headless_physics, load_cli_scene))
+        .add_systems(
+            PostUpdate,
+            crate::update_physics_progress.after(PhysicsSet::StepSimulation),
+        )
+        .add_systems(
+            PostUpdate,
+            record_step
+                .after(crate::update_physics_progress)
+                .after(PhysicsSet::Writeback),
+        );
+
+    if let AppExit::Error(code) = app.run() {
+        std::process::exit(code.get() as i32);
+    }
+}
+
+/// Queues the import of the scene file given with `--scene`, if any.
+pub fn load_cli_scene(
+    cli: Res<CliArgs>,
+    mut operations: ResMut<Operations>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let Some(path) = &cli.scene else {
+        return;
+    };
+
+    match read_scene(path) {
+        Ok(scene) => {
+            operations.push(Operation::ClearScene);
+            operations.push(Operation::ImportScene(scene));
+        }
+        Err(e) => {
+            error!("Failed to import scene: {:?}", e);
+            if cli.headless {
+                exit.send(AppExit::error());
+            }
+        }
+    }
+}
+
+fn read_scene(path: &Path) -> anyhow::Result<RapierContext> {
+    let data = std::fs::read(path)?;
+    Ok(serde_json::from_slice(&data)?)
+}
+
+fn setup_headless_physics(
+    cli: Res<CliArgs>,
+    mut config: ResMut<RapierConfiguration>,
+    mut timestep_mode: ResMut<TimestepMode>,
+    mut progress: ResMut<PhysicsProgress>,
+    context: Res<RapierContext>,
+) {
+    let dt = context.integration_parameters.dt;
+    // One step of fixed length per frame, regardless of the wall-clock time, so runs are
+    // reproducible.
+    *timestep_mode = TimestepMode::Fixed { dt, substeps: 1 };
+    config.physics_pipeline_active = true;
+
+    // Both limits are validated by clap, so the simulation runs at least one step.
+    let steps_until = cli.until.map(|t| (t / dt).ceil() as usize);
+    progress.progress_limit = match (cli.steps, steps_until) {
+        (Some(a), Some(b)) => a.min(b),
+        (a, b) => a.or(b).unwrap_or_default(),
+    };
+}
+
+fn record_step(
+    cli: Res<CliArgs>,
+    context: Res<RapierContext>,
+    progress: Res<PhysicsProgress>,
+    mut recording: ResMut<HeadlessRecording>,
+    mut exit: EventWriter<AppExit>,
+    bodies: Query<(Entity, &RapierRigidBodyHandle, Option<&Name>)>,
+) {
+    // Nothing to record until the scene was loaded and stepped.
+    if recording.steps.last().map(|s| s.step) == Some(progress.simulated_steps)
+        || progress.simulated_steps == 0
+    {
+        return;
+    }
+
+    let mut records: Vec<_> = bodies
+        .iter()
+        .filter_map(|(entity, handle, name)| {
+            let rb = context.bodies.get(handle.0)?;
+            #[cfg(feature = "dim2")]
+            let (rotation, angvel) = (rb.rotation().angle(), rb.angvel());
+            #[cfg(feature = "dim3")]
+            let (rotation, angvel) = (Quat::from(*rb.rotation()), Vect::from(*rb.angvel()));
+            Some(BodyRecord {
+                entity,
+                name: name.map(|n| n.to_string()),
+                translation: Vect::from(*rb.translation()),
+                rotation,
+                linvel: Vect::from(*rb.linvel()),
+                angvel,
+            })
+        })
+        .collect();
+    // Sort the bodies so the output doesn’t depend on the query iteration order.
+    records.sort_by_key(|r| r.entity);
+
+    recording.steps.push(StepRecord {
+        step: progress.simulated_steps,
+        time: progress.simulated_time,
+        timings: StepTimings::new(&context.pipeline.counters),
+        bodies: records,
+    });
+
+    if progress.simulated_steps >= progress.progress_limit {
+        let output = cli.output.as_deref().expect("--headless requires --output");
+        match write_recording(&recording, output) {
+            Ok(()) => {
+                info!("Simulation results written to {}", output.display());
+                exit.send(AppExit::Success);
+            }
+            Err(e) => {
+                error!("Failed to write simulation results: {:?}", e);
+                exit.send(AppExit::error());
+            }
+        }
+    }
+}
+
+/// Writes the recording as JSON if `path` has a `.json` extension, and as CSV otherwise.
+///
+/// In CSV, the trajectories are written to `path`, with one line per body and per step, and
+/// the timings to a second file with the `_timings` suffix, with one line per step.
+fn write_recording(recording: &HeadlessRecording, path: &Path) -> anyhow::Result<()> {
+    if path.extension().map(|ext| ext == "json") == Some(true) {
+        let data = serde_json::to_vec_pretty(&recording_json(recording))?;
+        std::fs::write(path, data)?;
+    } else {
+        let (trajectories, timings) = recording_csv(recording)?;
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let timings_path = path.with_file_name(format!("{stem}_timings.csv"));
+        std::fs::write(path, trajectories)?;
+        std::fs::write(timings_path, timings)?;
+    }
+    Ok(())
+}
+
+fn recording_json(recording: &HeadlessRecording) -> serde_json::Value {
+    let steps: Vec<_> = recording
+        .steps
+        .iter()
+        .map(|step| {
+            let timings: serde_json::Map<_, _> = StepTimings::NAMES
+                .iter()
+                .zip(step.timings.values())
+                .map(|(name, value)| (name.to_string(), value.into()))
+                .collect();
+            let bodies: Vec<_> = step
+                .bodies
+                .iter()
+                .map(|body| {
+                    serde_json::json!({
+                        "entity": body.entity.index(),
+                        "name": body.name,
+                        "translation": body.translation.to_array(),
+                        "rotation": rotation_values(body),
+                        "linvel": body.linvel.to_array(),
+                        "angvel": angvel_values(body),
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "step": step.step,
+                "time": step.time,
+                "timings": timings,
+                "bodies": bodies,
+            })
+        })
+        .collect();
+    serde_json::json!({ "steps": steps })
+}
+
+fn recording_csv(recording: &HeadlessRecording) -> anyhow::Result<(String, String)> {
+    #[cfg(feature = "dim2")]
+    const BODY_COLUMNS: &str = "x,y,rotation,linvel_x,linvel_y,angvel";
+    #[cfg(feature = "dim3")]
+    const BODY_COLUMNS: &str =
+        "x,y,z,rot_x,rot_y,rot_z,rot_w,linvel_x,linvel_y,linvel_z,angvel_x,angvel_y,angvel_z";
+
+    let mut trajectories = format!("step,time,entity,name,{BODY_COLUMNS}\n");
+    let mut timings = format!("step,time,{}\n", StepTimings::NAMES.join(","));
+
+    for step in &recording.steps {
+        for body in &step.bodies {
+            let values: Vec<_> = body
+                .translation
+                .to_array()
+                .into_iter()
+                .chain(rotation_values(body))
+                .chain(body.linvel.to_array())
+                .chain(angvel_values(body))
+                .map(|v| v.to_string())
+                .collect();
+            let name = body.name.as_deref().unwrap_or_default().replace(',', " ");
+            writeln!(
+                trajectories,
+                "{},{},{},{},{}",
+                step.step,
+                step.time,
+                body.entity.index(),
+                name,
+                values.join(",")
+            )?;
+        }
+
+        let values: Vec<_> = step
+            .timings
+            .values()
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        writeln!(timings, "{},{},{}", step.step, step.time, values.join(","))?;
+    }
+
+    Ok((trajectories, timings))
+}
+
+#[cfg(feature = "dim2")]
+fn rotation_values(body: &BodyRecord) -> Vec<f32> {
+    vec![body.rotation]
+}
+
+#[cfg(feature = "dim3")]
+fn rotation_values(body: &BodyRecord) -> Vec<f32> {
+    body.rotation.to_array().to_vec()
+}
+
+#[cfg(feature = "dim2")]
+fn angvel_values(body: &BodyRecord) -> Vec<f32> {
+    vec![body.angvel]
+}
+
+#[cfg(feature = "dim3")]
+fn angvel_values(body: &BodyRecord) -> Vec<f32> {
+    body.angvel.to_array().to_vec()
+}
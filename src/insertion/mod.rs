@@ -9,7 +9,11 @@ use bevy_rapier::rapier::math::DIM;
 use bevy_rapier::{geometry::Collider, math::Vect};
 
 use crate::render::RenderSystems;
-use crate::utils::{ColliderBundle, RigidBodyBundle};
+#[cfg(feature = "dim2")]
+use crate::ui::DrawShapeMode;
+#[cfg(feature = "dim3")]
+use crate::utils::polygon_is_convex;
+use crate::utils::{triangulate_polygon, ColliderBundle, PhysicsMaterial, RigidBodyBundle};
 #[cfg(feature = "dim3")]
 use bevy_polyline::prelude::*;
 use na::DMatrix;
@@ -22,11 +26,17 @@ mod mouse;
 #[derive(Component)]
 pub struct InsertionPreview;
 
+/// Preview of the polygon being drawn with the `DrawShape` tool.
+#[derive(Component)]
+pub struct DrawShapePreview;
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum InsertionStep {
     Basis,
     Height,
     Orientation,
+    /// The polygon vertices are being clicked out.
+    Polygon,
 }
 
 #[derive(Default, Clone, Resource)]
@@ -42,6 +52,7 @@ pub struct InsertionState {
     pub tool: SelectedTool,
     pub intersects_environment: bool,
     pub unlocked_scaling: bool,
+    pub polygon: Vec<Vect>,
 }
 
 impl InsertionState {
@@ -119,7 +130,7 @@ impl InsertionState {
         }
     }
 
-    pub fn operation(&self) -> Operation {
+    pub fn operation(&self, material: &PhysicsMaterial) -> Operation {
         let rigid_body = if self.on_empty_ground {
             RigidBody::Fixed
         } else {
@@ -180,15 +191,119 @@ impl InsertionState {
         }
 
         Operation::AddCollider(
-            ColliderBundle::new(collider),
-            RigidBodyBundle {
+            ColliderBundle::new(collider).with_material(material),
+            Some(RigidBodyBundle {
                 rigid_body,
                 ..Default::default()
-            },
+            }),
             transform,
         )
     }
 
+    /// The operation adding the polygon drawn with the `DrawShape` tool.
+    ///
+    /// Returns `None` if the polygon is degenerate or intersects itself.
+    #[cfg(feature = "dim2")]
+    pub fn polygon_operation(
+        &self,
+        mode: DrawShapeMode,
+        closed: bool,
+        material: &PhysicsMaterial,
+    ) -> Option<Operation> {
+        let center = self.polygon.iter().copied().sum::<Vect>() / self.polygon.len() as f32;
+        let vertices: Vec<_> = self.polygon.iter().map(|pt| *pt - center).collect();
+        let num_vertices = vertices.len() as u32;
+        let loop_indices = || -> Vec<[u32; 2]> {
+            (0..num_vertices)
+                .map(|i| [i, (i + 1) % num_vertices])
+                .collect()
+        };
+
+        let (collider, rigid_body) = match mode {
+            DrawShapeMode::ConvexPolygon => (Collider::convex_hull(&vertices)?, RigidBody::Dynamic),
+            DrawShapeMode::ConvexDecomposition => {
+                // Reject self-intersecting polygons.
+                triangulate_polygon(&vertices)?;
+                (
+                    Collider::convex_decomposition(&vertices, &loop_indices()),
+                    RigidBody::Dynamic,
+                )
+            }
+            // Polylines have no area, so they can only be used for fixed bodies.
+            DrawShapeMode::Polyline => (
+                Collider::polyline(vertices, closed.then(loop_indices)),
+                RigidBody::Fixed,
+            ),
+        };
+
+        Some(Operation::AddCollider(
+            ColliderBundle::new(collider).with_material(material),
+            Some(RigidBodyBundle {
+                rigid_body,
+                ..Default::default()
+            }),
+            Transform::from_xyz(center.x, center.y, 0.0),
+        ))
+    }
+
+    /// The operation adding the ground polygon drawn with the `DrawShape` tool, extruded
+    /// up to the current height.
+    ///
+    /// Returns `None` if the polygon is degenerate or intersects itself.
+    #[cfg(feature = "dim3")]
+    pub fn polygon_operation(&self, material: &PhysicsMaterial) -> Option<Operation> {
+        let points: Vec<_> = self.polygon.iter().map(|pt| pt.xz()).collect();
+        let center = points.iter().copied().sum::<Vec2>() / points.len() as f32;
+        let points: Vec<_> = points.iter().map(|pt| *pt - center).collect();
+        let half_height = self.height.abs() / 2.0;
+
+        if half_height < ACTIVE_EPS / 2.0 {
+            return None;
+        }
+
+        let prism = |base: &[Vec2]| {
+            let vertices: Vec<_> = base
+                .iter()
+                .flat_map(|pt| {
+                    [
+                        Vec3::new(pt.x, -half_height, pt.y),
+                        Vec3::new(pt.x, half_height, pt.y),
+                    ]
+                })
+                .collect();
+            Collider::convex_hull(&vertices)
+        };
+
+        // Concave polygons are split into triangles, each extruded into a convex prism.
+        let collider = if polygon_is_convex(&points) {
+            prism(&points)?
+        } else {
+            let parts = triangulate_polygon(&points)?
+                .iter()
+                .map(|tri| {
+                    let part = prism(&tri.map(|i| points[i as usize]))?;
+                    Some((Vec3::ZERO, Quat::IDENTITY, part))
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Collider::compound(parts)
+        };
+
+        let rigid_body = if self.on_empty_ground {
+            RigidBody::Fixed
+        } else {
+            RigidBody::Dynamic
+        };
+
+        Some(Operation::AddCollider(
+            ColliderBundle::new(collider).with_material(material),
+            Some(RigidBodyBundle {
+                rigid_body,
+                ..Default::default()
+            }),
+            Transform::from_xyz(center.x, self.polygon[0].y + self.height / 2.0, center.y),
+        ))
+    }
+
     pub fn set_tool(&mut self, tool: SelectedTool) {
         if tool != self.tool {
             self.tool = tool;
@@ -231,6 +346,10 @@ impl Plugin for InsertionPlugin {
             .add_systems(
                 Update,
                 mouse::update_preview_scale.in_set(RenderSystems::BeforeCommands),
+            )
+            .add_systems(
+                Update,
+                mouse::handle_draw_shape.in_set(RenderSystems::BeforeCommands),
             );
     }
 }
@@ -255,6 +374,21 @@ fn spawn_preview_entity(
         .spawn(polyline)
         .insert(InsertionPreview)
         .insert(Visibility::Hidden);
+
+    let polygon = PolylineBundle {
+        polyline: polylines.add(Polyline::default()),
+        material: polyline_materials.add(PolylineMaterial {
+            width: 20.0,
+            perspective: true,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    commands
+        .spawn(polygon)
+        .insert(DrawShapePreview)
+        .insert(Visibility::Hidden);
 }
 
 #[cfg(feature = "dim2")]
@@ -264,6 +398,12 @@ fn spawn_preview_entity(mut commands: Commands) {
         .insert(InsertionPreview)
         .insert(Visibility::Hidden)
         .insert(Name::new("Preview"));
+
+    commands
+        .spawn(preview_shape_bundle(Vect::ONE, Color::WHITE))
+        .insert(DrawShapePreview)
+        .insert(Visibility::Hidden)
+        .insert(Name::new("Polygon preview"));
 }
 
 #[cfg(feature = "dim2")]
@@ -290,3 +430,27 @@ pub fn preview_shape_bundle(
         Stroke::new(color, 0.01),
     )
 }
+
+#[cfg(feature = "dim2")]
+pub fn polygon_preview_bundle(
+    points: &[Vect],
+    color: Color,
+) -> (
+    bevy_prototype_lyon::entity::ShapeBundle,
+    bevy_prototype_lyon::prelude::Stroke,
+) {
+    use bevy_prototype_lyon::prelude::{GeometryBuilder, ShapeBundle, Stroke};
+
+    let polygon = bevy_prototype_lyon::shapes::Polygon {
+        points: points.to_vec(),
+        closed: false,
+    };
+
+    (
+        ShapeBundle {
+            path: GeometryBuilder::build_as(&polygon),
+            ..default()
+        },
+        Stroke::new(color, 0.01),
+    )
+}
@@ -21,6 +21,18 @@ pub fn handle_insertion_click(
 ) {
     let mut reset = false;
 
+    if ui_state.selected_tool == SelectedTool::DrawShape {
+        // Polygons are handled by `handle_draw_shape`, just make sure we don’t leave
+        // an insertion from another tool unfinished.
+        if insertion_state.tool != SelectedTool::DrawShape && insertion_state.step.is_some() {
+            insertion_state.step = None;
+            if let Ok((preview_entity, _)) = preview.get_single() {
+                commands.entity(preview_entity).insert(Visibility::Hidden);
+            }
+        }
+        return;
+    }
+
     match ui_state.selected_tool {
         SelectedTool::AddBall
         | SelectedTool::AddCuboid
@@ -154,7 +166,7 @@ pub fn handle_insertion_click(
                     } else {
                         #[cfg(feature = "dim2")]
                         {
-                            operations.push(insertion_state.operation());
+                            operations.push(insertion_state.operation(&ui_state.new_body_material));
                             reset = true;
                         }
                         #[cfg(feature = "dim3")]
@@ -168,7 +180,7 @@ pub fn handle_insertion_click(
                 #[cfg(feature = "dim3")]
                 Some(InsertionStep::Orientation) => {
                     if !insertion_state.intersects_environment {
-                        operations.push(insertion_state.operation());
+                        operations.push(insertion_state.operation(&ui_state.new_body_material));
                         reset = true;
                     }
                 }
@@ -0,0 +1,212 @@
+use crate::insertion::{DrawShapePreview, InsertionState, InsertionStep, ACTIVE_EPS};
+use crate::operation::Operations;
+use crate::selection::SceneMouse;
+use crate::styling::Theme;
+use crate::ui::{ActiveMouseAction, SelectedTool, UiState};
+use bevy::prelude::*;
+use bevy_rapier::math::Vect;
+
+#[cfg(feature = "dim3")]
+use bevy_polyline::prelude::Polyline;
+
+/// Clicking closer than this to the first vertex closes the polygon.
+const CLOSING_DISTANCE: f32 = 0.2;
+
+/// Handles the `DrawShape` tool.
+///
+/// Each left click adds a vertex to the polygon. The polygon is finished by clicking on
+/// its first vertex, or by pressing Enter (which leaves polylines open). In 3D, the
+/// polygon is then extruded up to the height selected by the next click.
+pub fn handle_draw_shape(
+    mut commands: Commands,
+    mut insertion_state: ResMut<InsertionState>,
+    mut operations: ResMut<Operations>,
+    mut mouse_action: ResMut<ActiveMouseAction>,
+    ui_state: Res<UiState>,
+    theme: Res<Theme>,
+    scene_mouse: Res<SceneMouse>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    preview: Query<Entity, With<DrawShapePreview>>,
+    #[cfg(feature = "dim3")] preview_polylines: Query<&Handle<Polyline>, With<DrawShapePreview>>,
+    #[cfg(feature = "dim3")] mut polylines: ResMut<Assets<Polyline>>,
+) {
+    let Ok(preview_entity) = preview.get_single() else {
+        return;
+    };
+    let drawing = insertion_state.tool == SelectedTool::DrawShape && insertion_state.step.is_some();
+
+    if ui_state.selected_tool != SelectedTool::DrawShape
+        || (*mouse_action != ActiveMouseAction::Insertion
+            && *mouse_action != ActiveMouseAction::None)
+        || mouse.just_pressed(MouseButton::Right)
+    {
+        if drawing {
+            reset(&mut insertion_state, &mut mouse_action);
+            commands.entity(preview_entity).insert(Visibility::Hidden);
+        }
+        return;
+    }
+
+    #[cfg(feature = "dim2")]
+    let cursor = scene_mouse.point;
+    #[cfg(feature = "dim3")]
+    let cursor = scene_mouse.ray.and_then(|(ray_pos, ray_dir)| {
+        // Cast against the ground.
+        let ground_hit = ray_pos.y / -ray_dir.y;
+        (ray_dir.y.abs() > 1.0e-3 && ground_hit > 0.0).then(|| ray_pos + ray_dir * ground_hit)
+    });
+
+    let mut finished = None;
+
+    match insertion_state.step {
+        None => {
+            if let Some(point) = cursor.filter(|_| mouse.just_pressed(MouseButton::Left)) {
+                insertion_state.set_tool(SelectedTool::DrawShape);
+                insertion_state.polygon = vec![point];
+                insertion_state.step = Some(InsertionStep::Polygon);
+                // Like the other insertion tools, shapes started away from any collider are
+                // attached to the ground.
+                #[cfg(feature = "dim2")]
+                {
+                    insertion_state.on_empty_ground = false;
+                }
+                #[cfg(feature = "dim3")]
+                {
+                    insertion_state.on_empty_ground = scene_mouse.hovered.is_none();
+                }
+                *mouse_action = ActiveMouseAction::Insertion;
+                commands.entity(preview_entity).insert(Visibility::Visible);
+            }
+        }
+        Some(InsertionStep::Polygon) => {
+            if let Some(point) = cursor.filter(|_| mouse.just_pressed(MouseButton::Left)) {
+                let first = insertion_state.polygon[0];
+                let last = *insertion_state.polygon.last().unwrap();
+
+                if insertion_state.polygon.len() >= 3 && (point - first).length() < CLOSING_DISTANCE
+                {
+                    finished = Some(true);
+                } else if (point - last).length() >= ACTIVE_EPS {
+                    insertion_state.polygon.push(point);
+                }
+            }
+
+            if keyboard.just_pressed(KeyCode::Enter) {
+                finished = Some(false);
+            }
+        }
+        #[cfg(feature = "dim3")]
+        Some(InsertionStep::Height) => {
+            if mouse.just_pressed(MouseButton::Left) {
+                if let Some(op) = insertion_state.polygon_operation(&ui_state.new_body_material) {
+                    operations.push(op);
+                }
+                reset(&mut insertion_state, &mut mouse_action);
+                commands.entity(preview_entity).insert(Visibility::Hidden);
+                return;
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(closed) = finished {
+        #[cfg(feature = "dim2")]
+        {
+            let min_len = if ui_state.draw_shape_mode == crate::ui::DrawShapeMode::Polyline {
+                2
+            } else {
+                3
+            };
+
+            if insertion_state.polygon.len() >= min_len {
+                match insertion_state.polygon_operation(
+                    ui_state.draw_shape_mode,
+                    closed,
+                    &ui_state.new_body_material,
+                ) {
+                    Some(op) => operations.push(op),
+                    None => warn!("Failed to create a collider from the drawn polygon."),
+                }
+            }
+
+            reset(&mut insertion_state, &mut mouse_action);
+            commands.entity(preview_entity).insert(Visibility::Hidden);
+            return;
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            let _ = closed;
+            if insertion_state.polygon.len() >= 3 {
+                // Extrude from the polygon center, along the ground normal.
+                let center = insertion_state.polygon.iter().copied().sum::<Vect>()
+                    / insertion_state.polygon.len() as f32;
+                insertion_state.start_point = center;
+                insertion_state.end_point = center;
+                insertion_state.basis = [Vec3::X, Vec3::Y, Vec3::Z];
+                insertion_state.height = 0.0;
+                insertion_state.step = Some(InsertionStep::Height);
+            } else {
+                reset(&mut insertion_state, &mut mouse_action);
+                commands.entity(preview_entity).insert(Visibility::Hidden);
+                return;
+            }
+        }
+    }
+
+    // Update the preview.
+    let mut points = insertion_state.polygon.clone();
+    if insertion_state.step == Some(InsertionStep::Polygon) {
+        points.extend(cursor);
+    }
+
+    #[cfg(feature = "dim2")]
+    if insertion_state.step.is_some() {
+        commands
+            .entity(preview_entity)
+            .insert(crate::insertion::polygon_preview_bundle(
+                &points,
+                theme.insertion_preview_color(),
+            ))
+            .insert(Visibility::Visible);
+    }
+
+    #[cfg(feature = "dim3")]
+    {
+        let _ = &theme;
+        if insertion_state.step == Some(InsertionStep::Height) {
+            points = prism_polyline(&points, insertion_state.height);
+        }
+
+        if let Some(polyline) = preview_polylines
+            .get_single()
+            .ok()
+            .and_then(|handle| polylines.get_mut(handle))
+        {
+            polyline.vertices = points;
+        }
+    }
+}
+
+fn reset(insertion_state: &mut InsertionState, mouse_action: &mut ActiveMouseAction) {
+    insertion_state.step = None;
+    insertion_state.polygon.clear();
+    if *mouse_action == ActiveMouseAction::Insertion {
+        *mouse_action = ActiveMouseAction::None;
+    }
+}
+
+/// A single line strip going through every edge of the extruded polygon.
+#[cfg(feature = "dim3")]
+fn prism_polyline(base: &[Vect], height: f32) -> Vec<Vect> {
+    let top: Vec<_> = base.iter().map(|pt| *pt + Vect::Y * height).collect();
+    let mut vertices: Vec<_> = base.to_vec();
+    vertices.push(base[0]);
+
+    for i in 0..base.len() {
+        vertices.extend([top[i], base[i], top[i], top[(i + 1) % base.len()]]);
+    }
+
+    vertices
+}
@@ -1,5 +1,7 @@
 pub use self::click::*;
+pub use self::draw_shape::*;
 pub use self::hover::*;
 
 mod click;
+mod draw_shape;
 mod hover;
@@ -0,0 +1,24 @@
+use crate::render::RenderSystems;
+use bevy::prelude::*;
+use bevy_rapier::math::Vect;
+
+mod mouse;
+
+#[derive(Default, Clone, Resource)]
+pub struct JointState {
+    /// The first body picked, with the world-space anchor and surface normal picked on it.
+    pub first: Option<(Entity, Vect, Vect)>,
+}
+
+pub struct JointPlugin;
+
+impl Plugin for JointPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(JointState::default())
+            .add_systems(
+                Update,
+                mouse::handle_joint_click.in_set(RenderSystems::BeforeCommands),
+            )
+            .add_systems(Update, mouse::handle_joint_hover);
+    }
+}
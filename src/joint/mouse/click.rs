@@ -0,0 +1,65 @@
+use crate::joint::JointState;
+use crate::operation::{Operation, Operations};
+use crate::selection::{SceneMouse, SelectableSceneObject};
+use crate::ui::{SelectedTool, UiState};
+use bevy::prelude::*;
+use bevy_rapier::math::Vect;
+use bevy_rapier::plugin::RapierContext;
+
+/// Handles the `AddJoint` tool.
+///
+/// The first click picks the first body and an anchor on its surface, the second click
+/// picks the second body and its anchor, and attaches both bodies with a joint.
+pub fn handle_joint_click(
+    mut joint_state: ResMut<JointState>,
+    mut operations: ResMut<Operations>,
+    ui_state: Res<UiState>,
+    scene_mouse: Res<SceneMouse>,
+    context: Res<RapierContext>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+) {
+    if ui_state.selected_tool != SelectedTool::AddJoint
+        || mouse.just_pressed(MouseButton::Right)
+        || keyboard.just_pressed(KeyCode::Escape)
+    {
+        joint_state.first = None;
+        return;
+    }
+
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let Some(SelectableSceneObject::Collider(entity, hit)) = scene_mouse.hovered else {
+        return;
+    };
+
+    #[cfg(feature = "dim2")]
+    let (anchor, normal) = (hit, Vect::X);
+    #[cfg(feature = "dim3")]
+    let (anchor, normal) = (hit.point, hit.normal);
+
+    // Attach the joint to the body the collider is attached to.
+    let body = context.collider_parent(entity).unwrap_or(entity);
+
+    match joint_state.first {
+        None => joint_state.first = Some((body, anchor, normal)),
+        Some((first_body, first_anchor, first_normal)) => {
+            if first_body == body {
+                return;
+            }
+
+            operations.push(Operation::AddJoint {
+                body1: first_body,
+                body2: body,
+                anchor1: first_anchor,
+                anchor2: anchor,
+                normal: first_normal,
+                kind: ui_state.joint_kind,
+                multibody: ui_state.multibody_joint,
+            });
+            joint_state.first = None;
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::joint::JointState;
+use crate::selection::SceneMouse;
+use crate::styling::Theme;
+use bevy::prelude::*;
+
+#[cfg(feature = "dim3")]
+use crate::selection::SelectableSceneObject;
+
+/// Draws the link between the first anchor picked with the `AddJoint` tool and the cursor.
+pub fn handle_joint_hover(
+    mut gizmos: Gizmos,
+    joint_state: Res<JointState>,
+    scene_mouse: Res<SceneMouse>,
+    theme: Res<Theme>,
+) {
+    let Some((_, anchor, _)) = joint_state.first else {
+        return;
+    };
+    let color = theme.insertion_preview_color();
+
+    #[cfg(feature = "dim2")]
+    {
+        gizmos.circle_2d(anchor, 0.05, color);
+        if let Some(point) = scene_mouse.point {
+            gizmos.line_2d(anchor, point, color);
+        }
+    }
+
+    #[cfg(feature = "dim3")]
+    {
+        gizmos.sphere(anchor, Quat::IDENTITY, 0.05, color);
+        let target = match scene_mouse.hovered {
+            Some(SelectableSceneObject::Collider(_, inter)) => Some(inter.point),
+            _ => None,
+        };
+        if let Some(target) = target {
+            gizmos.line(anchor, target, color);
+        }
+    }
+}
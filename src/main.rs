@@ -37,7 +37,10 @@ mod utils;
 mod builtin_scenes;
 mod cli;
 mod control;
+mod cut;
 mod drag;
+mod headless;
+mod joint;
 mod layers;
 mod projectile;
 
@@ -62,6 +65,11 @@ pub enum SteadyumStages {
 fn main() {
     let args = CliArgs::parse();
 
+    if args.headless {
+        headless::run(args);
+        return;
+    }
+
     // let title = if cfg!(feature = "dim2") {
     //     "Steadyum 2D".to_string()
     // } else {
@@ -90,11 +98,14 @@ fn main() {
         .add_plugins(ui::RapierUiPlugin)
         .add_plugins(styling::StylingPlugin)
         .add_plugins(operation::RapierOperationsPlugin)
+        .add_plugins(operation::RapierHistoryPlugin)
         // .add_plugins(bevy_prototype_lyon::prelude::ShapePlugin)
         .add_plugins(insertion::InsertionPlugin)
         .add_plugins(floor::FloorPlugin)
         .add_plugins(drag::DragPlugin)
         .add_plugins(projectile::ProjectilePlugin)
+        .add_plugins(cut::CutPlugin)
+        .add_plugins(joint::JointPlugin)
         .add_plugins(control::ControlPlugin)
         .add_plugins(OrbitCameraPlugin)
         // .add_stage_after(
@@ -105,7 +116,12 @@ fn main() {
         // .add_startup_system(set_window_icon)
         .add_systems(Startup, init_profiling_and_gravity)
         .add_systems(Startup, setup_graphics)
-        .add_systems(Startup, setup_physics);
+        .add_systems(Startup, setup_physics)
+        .add_systems(Startup, headless::load_cli_scene)
+        .add_systems(
+            PostUpdate,
+            update_physics_progress.after(PhysicsSet::StepSimulation),
+        );
 
     app.add_plugins(bevy_polyline::PolylinePlugin);
 
@@ -126,6 +142,17 @@ fn init_profiling_and_gravity(
     physics.pipeline.counters.enable();
 }
 
+fn update_physics_progress(
+    config: Res<RapierConfiguration>,
+    physics: Res<RapierContext>,
+    mut progress: ResMut<PhysicsProgress>,
+) {
+    if config.physics_pipeline_active {
+        progress.simulated_steps += 1;
+        progress.simulated_time += physics.integration_parameters.dt;
+    }
+}
+
 fn set_window_icon(windows: NonSendMut<WinitWindows>) {
     /*
     let primary = windows.get_window(WindowId::primary()).unwrap();
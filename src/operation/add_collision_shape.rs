@@ -10,12 +10,14 @@ pub fn add_collision_shape(
 ) {
     for op in operations.iter() {
         if let Operation::AddCollider(collider, rigid_body, transform) = op {
-            commands
-                .spawn(collider.clone())
-                .insert(rigid_body.clone())
+            let mut entity = commands.spawn(collider.clone());
+            entity
                 .insert(Name::new("Collision Shape"))
                 .insert(TransformBundle::from_transform(*transform))
                 .insert(ColliderRenderBundle::new(&mut colors));
+            if let Some(rigid_body) = rigid_body {
+                entity.insert(*rigid_body);
+            }
         }
     }
 }
@@ -1,168 +1,588 @@
-use crate::operation::Operations;
+use crate::operation::{Operation, Operations};
+use crate::render::ColliderRender;
+use crate::selection::{Selection, SelectionOrder};
+use crate::utils::{self, ColliderBundle, ColliderRenderBundle, RigidBodyBundle};
 use bevy::prelude::*;
+use bevy_rapier::parry::shape::{Shape, SharedShape, TypedShape};
 use bevy_rapier::prelude::*;
+use bevy_rapier::rapier::geometry::ColliderHandle;
+use bevy_rapier::rapier::math::{Isometry, Point};
 
+#[cfg(feature = "dim2")]
+use {
+    super::cut::convex_points, bevy::sprite::MaterialMesh2dBundle,
+    bevy_rapier::rapier::math::Vector,
+};
 #[cfg(feature = "dim3")]
 use {
-    crate::operation::Operation, crate::utils, bevy::pbr::wireframe::Wireframe,
-    bevy_rapier::parry::query::SplitResult, bevy_rapier::rapier::math::Isometry,
+    bevy::pbr::wireframe::Wireframe,
+    bevy_rapier::parry::query::SplitResult,
+    bevy_rapier::parry::shape::{HalfSpace, TriMesh, TriMeshFlags},
 };
 
-#[derive(Component)]
-pub struct PersistentIntersection(Entity, Entity);
+#[cfg(feature = "dim3")]
+const NSUB: u32 = 16;
 
-#[cfg(feature = "dim2")] // TODO: not implemented in 2D yet.
-pub fn add_intersection(
-    mut _commands: Commands,
-    _operations: Res<Operations>,
-    _colliders: Query<(Entity, &Collider)>,
-) {
+/// Boolean operation between two colliders.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BooleanOp {
+    Union,
+    Intersection,
+    /// Subtracts the second collider from the first one.
+    Difference,
 }
 
-#[cfg(feature = "dim2")] // TODO: not implemented in 2D yet.
-pub fn update_intersection(
-    mut _commands: Commands,
-    mut _meshes: ResMut<Assets<Mesh>>,
-    _intersections: Query<(Entity, &PersistentIntersection)>,
-    _transforms: Query<&Transform, Changed<Transform>>,
-    _global_transforms: Query<&GlobalTransform>,
-    _shapes: Query<&Collider>,
-) {
-}
+/// A live preview of a boolean operation between two colliders, recomputed whenever one
+/// of them moves.
+#[derive(Component)]
+pub struct PersistentIntersection(Entity, Entity, BooleanOp);
 
-#[cfg(feature = "dim3")]
+/// Applies a boolean operation on the two selected colliders.
+///
+/// The colliders are ordered by selection order, so `BooleanOp::Difference` subtracts the
+/// second selected collider from the first one.
+///
+/// Baking the result despawns both colliders, as well as the bodies they leave without any
+/// collider.
 pub fn add_intersection(
     mut commands: Commands,
     operations: Res<Operations>,
-    colliders: Query<(Entity, &Collider)>,
+    context: Res<RapierContext>,
+    order: Res<SelectionOrder>,
+    colliders: Query<(
+        Entity,
+        &Collider,
+        &GlobalTransform,
+        &Selection,
+        Option<&ColliderRender>,
+    )>,
 ) {
     for op in operations.iter() {
-        if let Operation::AddIntersection = op {
-            // FIXME: this is just a very specialized version to test
-            // the plane/mesh splitting.
-            let mut trimesh = None;
-            let mut plane = None;
-
-            for (entity, collider) in colliders.iter() {
-                let shape = collider.as_typed_shape();
-                if matches!(shape, ColliderView::TriMesh { .. }) {
-                    if trimesh.is_some() {
-                        plane = Some(entity);
-                    } else {
-                        trimesh = Some(entity);
-                    }
-                }
+        let Operation::AddIntersection { op, bake } = op else {
+            continue;
+        };
+
+        let mut selected: Vec<_> = colliders
+            .iter()
+            .filter(|(_, _, _, selection, _)| selection.selected())
+            .collect();
+        selected.sort_by_key(|(entity, ..)| (order.rank(*entity).unwrap_or(usize::MAX), *entity));
+
+        let [(entity1, collider1, transform1, _, render), (entity2, collider2, transform2, _, _)] =
+            selected[..]
+        else {
+            warn!(
+                "Boolean operations need exactly two selected colliders, found {}.",
+                selected.len()
+            );
+            continue;
+        };
+
+        if !*bake {
+            commands.spawn(PersistentIntersection(entity1, entity2, *op));
+            continue;
+        }
+
+        let co = context
+            .entity2collider()
+            .get(&entity1)
+            .and_then(|h| context.colliders.get(*h));
+        let Some(co) = co else {
+            continue;
+        };
+        let body = co
+            .parent()
+            .and_then(|h| context.bodies.get(h))
+            .map(RigidBodyBundle::from)
+            .unwrap_or_else(RigidBodyBundle::fixed);
 
-                if matches!(shape, ColliderView::HalfSpace { .. })
-                    || matches!(shape, ColliderView::Cuboid { .. })
-                {
-                    plane = Some(entity);
+        let pos1 = transform_to_iso(transform1);
+        let pos2 = transform_to_iso(transform2);
+        let dynamic = body.rigid_body == RigidBody::Dynamic;
+
+        let shape =
+            match boolean_shape(*op, &pos1, &*collider1.raw, &pos2, &*collider2.raw, dynamic) {
+                Ok(Some(shape)) => shape,
+                Ok(None) => {
+                    warn!("The result of the boolean operation is empty.");
+                    continue;
                 }
-            }
+                Err(e) => {
+                    error!("Failed to compute the boolean operation: {:?}", e);
+                    continue;
+                }
+            };
 
-            if let (Some(trimesh), Some(plane)) = (trimesh, plane) {
-                commands.spawn(PersistentIntersection(trimesh, plane));
-            }
+        let color = render.map(|r| r.color).unwrap_or_default();
+        let collider = ColliderBundle {
+            collider: Collider::from(shape),
+            mass_properties: ColliderMassProperties::Density(co.density()),
+            ..ColliderBundle::from(co)
+        };
+        let rigid_body = RigidBodyBundle {
+            additional_mass_properties: AdditionalMassProperties::default(),
+            ..body
+        };
+
+        commands
+            .spawn(collider)
+            .insert(rigid_body)
+            .insert(Name::new(format!("{:?}", op)))
+            .insert(TransformBundle::from_transform(
+                transform1.compute_transform(),
+            ))
+            .insert(ColliderRenderBundle::with_color(color));
+
+        let consumed = |handle: &ColliderHandle| {
+            context
+                .collider_entity(*handle)
+                .is_some_and(|entity| entity == entity1 || entity == entity2)
+        };
+        let mut despawned: Vec<_> = [entity1, entity2]
+            .into_iter()
+            .map(|entity| {
+                // Child colliders are despawned with their body if it has no other collider.
+                let emptied_body = context
+                    .collider_parent(entity)
+                    .filter(|body| *body != entity)
+                    .filter(|body| {
+                        context
+                            .entity2body()
+                            .get(body)
+                            .and_then(|h| context.bodies.get(*h))
+                            .is_some_and(|rb| rb.colliders().iter().all(consumed))
+                    });
+                emptied_body.unwrap_or(entity)
+            })
+            .collect();
+        despawned.dedup();
+
+        for entity in despawned {
+            commands.entity(entity).despawn_recursive();
         }
     }
 }
 
-#[cfg(feature = "dim3")]
 pub fn update_intersection(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
-    intersections: Query<(Entity, &PersistentIntersection)>,
+    #[cfg(feature = "dim2")] mut materials: ResMut<Assets<ColorMaterial>>,
+    intersections: Query<(Entity, Ref<PersistentIntersection>)>,
     global_transforms: Query<Ref<GlobalTransform>>,
     shapes: Query<&Collider>,
 ) {
     for (entity, intersection) in intersections.iter() {
-        if let (Ok(t1), Ok(t2)) = (
+        let (Ok(t1), Ok(t2), Ok(shape1), Ok(shape2)) = (
             global_transforms.get(intersection.0),
             global_transforms.get(intersection.1),
-        ) {
-            let t1_changed = t1.is_changed();
-            let t2_changed = t2.is_changed();
-            let t1 = t1.compute_transform();
-            let t2 = t2.compute_transform();
-
-            if t1_changed || t2_changed {
-                if let (Ok(shape1), Ok(shape2)) =
-                    (shapes.get(intersection.0), shapes.get(intersection.1))
-                {
-                    // Compute the intersection.
-                    let mesh1 = shape1.as_trimesh().unwrap();
-                    let mesh1_pos: Isometry<f32> = (t1.translation, t1.rotation).into();
-
-                    if let Some(mesh2) = shape2.as_trimesh() {
-                        let mesh2_pos: Isometry<f32> = (t2.translation, t2.rotation).into();
-
-                        match crate::parry::transformation::intersect_meshes(
-                            &mesh1_pos, &mesh1.raw, false, &mesh2_pos, &mesh2.raw, false,
-                        ) {
-                            Ok(Some(result)) => {
-                                let bundle = utils::bevy_pbr_bundle_from_trimesh(
-                                    &mut meshes,
-                                    &result,
-                                    mesh1_pos,
-                                );
-                                commands.entity(entity).insert(bundle).insert(Wireframe);
-                            }
-                            Ok(None) => {
-                                commands.entity(entity).remove::<PbrBundle>();
-                            }
-                            Err(err) => error!("mesh intersection failed {}", err),
-                        }
-                    }
-
-                    if let Some(plane) = shape2.as_halfspace() {
-                        let plane_pos: Isometry<f32> = (t2.translation, t2.rotation).into();
-                        let axis = plane_pos * plane.raw.normal;
-                        let bias = plane_pos.translation.vector.dot(&axis);
-
-                        match mesh1.raw.split(&mesh1_pos, &axis, bias, 1.0e-5) {
-                            SplitResult::Pair(piece, _) => {
-                                let bundle = utils::bevy_pbr_bundle_from_trimesh(
-                                    &mut meshes,
-                                    &piece,
-                                    mesh1_pos,
-                                );
-                                commands.entity(entity).insert(bundle).insert(Wireframe);
-                            }
-                            SplitResult::Negative => {
-                                let bundle = utils::bevy_pbr_bundle_from_trimesh(
-                                    &mut meshes,
-                                    &mesh1.raw,
-                                    mesh1_pos,
-                                );
-                                commands.entity(entity).insert(bundle).insert(Wireframe);
-                            }
-                            _ => {
-                                commands.entity(entity).remove::<PbrBundle>();
-                            }
-                        };
-                    }
-
-                    if let Some(cuboid) = shape2.as_cuboid() {
-                        let cuboid_pos: Isometry<f32> = (t2.translation, t2.rotation).into();
-
-                        if let Some(intersection) = mesh1.raw.intersection_with_cuboid(
-                            &mesh1_pos,
-                            false,
-                            &cuboid.raw,
-                            &cuboid_pos,
-                            false,
-                            1.0e-5,
-                        ) {
-                            let bundle = utils::bevy_pbr_bundle_from_trimesh(
-                                &mut meshes,
-                                &intersection,
-                                mesh1_pos,
-                            );
-                            commands.entity(entity).insert(bundle).insert(Wireframe);
-                        }
-                    }
+            shapes.get(intersection.0),
+            shapes.get(intersection.1),
+        ) else {
+            // One of the operands was removed.
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+
+        if !intersection.is_added() && !t1.is_changed() && !t2.is_changed() {
+            continue;
+        }
+
+        let pos1 = transform_to_iso(&t1);
+        let pos2 = transform_to_iso(&t2);
+
+        #[cfg(feature = "dim2")]
+        match boolean_polygons(intersection.2, &pos1, &*shape1.raw, &pos2, &*shape2.raw) {
+            Ok(polygons) if !polygons.is_empty() => {
+                let mut vertices = vec![];
+                let mut indices = vec![];
+                for polygon in &polygons {
+                    let base = vertices.len() as u32;
+                    vertices.extend_from_slice(polygon);
+                    indices.extend(
+                        (1..polygon.len() as u32 - 1).map(|i| [base, base + i, base + i + 1]),
+                    );
                 }
+
+                let mesh = utils::bevy_mesh_from_trimesh_elements(&vertices, Some(indices));
+                let mut transform = bevy_rapier::utils::iso_to_transform(&pos1);
+                // Display the preview on top of the colliders.
+                transform.translation.z = 1.0;
+                commands.entity(entity).insert(MaterialMesh2dBundle {
+                    mesh: meshes.add(mesh).into(),
+                    material: materials.add(ColorMaterial::from(Color::WHITE.with_alpha(0.5))),
+                    transform,
+                    ..Default::default()
+                });
             }
+            Ok(_) => {
+                commands
+                    .entity(entity)
+                    .remove::<MaterialMesh2dBundle<ColorMaterial>>();
+            }
+            Err(err) => error!("Failed to compute the boolean operation: {:?}", err),
+        }
+
+        #[cfg(feature = "dim3")]
+        match boolean_trimesh(intersection.2, &pos1, &*shape1.raw, &pos2, &*shape2.raw) {
+            Ok(Some(result)) => {
+                let bundle = utils::bevy_pbr_bundle_from_trimesh(&mut meshes, &result, pos1);
+                commands.entity(entity).insert(bundle).insert(Wireframe);
+            }
+            Ok(None) => {
+                commands.entity(entity).remove::<PbrBundle>();
+            }
+            Err(err) => error!("Failed to compute the boolean operation: {:?}", err),
         }
     }
 }
+
+#[cfg(feature = "dim2")]
+fn transform_to_iso(transform: &GlobalTransform) -> Isometry<Real> {
+    let (_, rotation, translation) = transform.to_scale_rotation_translation();
+    Isometry::new(translation.truncate().into(), rotation.to_scaled_axis().z)
+}
+
+#[cfg(feature = "dim3")]
+fn transform_to_iso(transform: &GlobalTransform) -> Isometry<Real> {
+    let (_, rotation, translation) = transform.to_scale_rotation_translation();
+    (translation, rotation).into()
+}
+
+/// The shape resulting from a boolean operation, in the local-space of the first shape.
+///
+/// In 3D, triangle meshes can’t be used by dynamic bodies so the result is decomposed into
+/// convex parts if `dynamic` is `true`.
+#[cfg(feature = "dim2")]
+fn boolean_shape(
+    op: BooleanOp,
+    pos1: &Isometry<Real>,
+    shape1: &dyn Shape,
+    pos2: &Isometry<Real>,
+    shape2: &dyn Shape,
+    _dynamic: bool,
+) -> anyhow::Result<Option<SharedShape>> {
+    let parts: Vec<_> = boolean_polygons(op, pos1, shape1, pos2, shape2)?
+        .iter()
+        .filter_map(|polygon| Some((Isometry::identity(), SharedShape::convex_hull(polygon)?)))
+        .collect();
+    Ok((!parts.is_empty()).then(|| SharedShape::compound(parts)))
+}
+
+/// The shape resulting from a boolean operation, in the local-space of the first shape.
+///
+/// In 3D, triangle meshes can’t be used by dynamic bodies so the result is decomposed into
+/// convex parts if `dynamic` is `true`.
+#[cfg(feature = "dim3")]
+fn boolean_shape(
+    op: BooleanOp,
+    pos1: &Isometry<Real>,
+    shape1: &dyn Shape,
+    pos2: &Isometry<Real>,
+    shape2: &dyn Shape,
+    dynamic: bool,
+) -> anyhow::Result<Option<SharedShape>> {
+    let Some(result) = boolean_trimesh(op, pos1, shape1, pos2, shape2)? else {
+        return Ok(None);
+    };
+
+    let vertices = result.vertices().to_vec();
+    let indices = result.indices().to_vec();
+    if dynamic {
+        Ok(Some(SharedShape::convex_decomposition(&vertices, &indices)))
+    } else {
+        Ok(Some(SharedShape::trimesh_with_flags(
+            vertices,
+            indices,
+            trimesh_flags(),
+        )))
+    }
+}
+
+#[cfg(feature = "dim3")]
+fn trimesh_flags() -> TriMeshFlags {
+    TriMeshFlags::HALF_EDGE_TOPOLOGY
+        | TriMeshFlags::CONNECTED_COMPONENTS
+        | TriMeshFlags::ORIENTED
+        | TriMeshFlags::MERGE_DUPLICATE_VERTICES
+        | TriMeshFlags::DELETE_DEGENERATE_TRIANGLES
+}
+
+/// Computes a boolean operation between the triangle meshes of two shapes.
+///
+/// The result is expressed in the local-space of the first shape.
+#[cfg(feature = "dim3")]
+fn boolean_trimesh(
+    op: BooleanOp,
+    pos1: &Isometry<Real>,
+    shape1: &dyn Shape,
+    pos2: &Isometry<Real>,
+    shape2: &dyn Shape,
+) -> anyhow::Result<Option<TriMesh>> {
+    // Half-spaces are infinite, so the other shape is split by their boundary plane instead.
+    if let Some(plane) = shape2.as_shape::<HalfSpace>() {
+        let Some(mesh1) = shape_trimesh(shape1) else {
+            anyhow::bail!("unsupported shape type");
+        };
+        let mesh1 = TriMesh::with_flags(mesh1.0, mesh1.1, trimesh_flags());
+        return match op {
+            BooleanOp::Intersection => Ok(split_by_half_space(pos1, &mesh1, pos2, plane, true)),
+            BooleanOp::Difference => Ok(split_by_half_space(pos1, &mesh1, pos2, plane, false)),
+            BooleanOp::Union => anyhow::bail!("the union with a half-space is infinite"),
+        };
+    }
+    if let Some(plane) = shape1.as_shape::<HalfSpace>() {
+        let Some(mesh2) = shape_trimesh(shape2) else {
+            anyhow::bail!("unsupported shape type");
+        };
+        let mesh2 = TriMesh::with_flags(mesh2.0, mesh2.1, trimesh_flags());
+        return match op {
+            BooleanOp::Intersection => {
+                let mut result = split_by_half_space(pos2, &mesh2, pos1, plane, true);
+                if let Some(result) = &mut result {
+                    result.transform_vertices(&pos1.inv_mul(pos2));
+                }
+                Ok(result)
+            }
+            _ => anyhow::bail!("the result of this operation with a half-space is infinite"),
+        };
+    }
+
+    let (Some(mesh1), Some(mesh2)) = (shape_trimesh(shape1), shape_trimesh(shape2)) else {
+        anyhow::bail!("unsupported shape type");
+    };
+    let mesh1 = TriMesh::with_flags(mesh1.0, mesh1.1, trimesh_flags());
+    let mesh2 = TriMesh::with_flags(mesh2.0, mesh2.1, trimesh_flags());
+
+    // Flipping a mesh selects the outside of the shape instead of its inside. The union is
+    // the complement of the intersection of both complements.
+    let (flip1, flip2) = match op {
+        BooleanOp::Union => (true, true),
+        BooleanOp::Intersection => (false, false),
+        BooleanOp::Difference => (false, true),
+    };
+
+    let mut result =
+        crate::parry::transformation::intersect_meshes(pos1, &mesh1, flip1, pos2, &mesh2, flip2)?;
+
+    if op == BooleanOp::Union {
+        if let Some(result) = &mut result {
+            result.reverse();
+        }
+    }
+
+    Ok(result)
+}
+
+/// The part of a mesh inside of a half-space if `inside` is `true`, or outside of it
+/// otherwise.
+///
+/// The result is expressed in the local-space of the mesh.
+#[cfg(feature = "dim3")]
+fn split_by_half_space(
+    mesh_pos: &Isometry<Real>,
+    mesh: &TriMesh,
+    plane_pos: &Isometry<Real>,
+    plane: &HalfSpace,
+    inside: bool,
+) -> Option<TriMesh> {
+    let plane_pos = mesh_pos.inv_mul(plane_pos);
+    let axis = plane_pos * plane.normal;
+    let bias = plane_pos.translation.vector.dot(&axis);
+
+    match mesh.local_split(&axis, bias, 1.0e-5) {
+        SplitResult::Pair(negative, positive) => Some(if inside { negative } else { positive }),
+        SplitResult::Negative => inside.then(|| mesh.clone()),
+        SplitResult::Positive => (!inside).then(|| mesh.clone()),
+    }
+}
+
+/// The vertices and indices of the triangle mesh approximating a shape.
+#[cfg(feature = "dim3")]
+fn shape_trimesh(shape: &dyn Shape) -> Option<(Vec<Point<Real>>, Vec<[u32; 3]>)> {
+    let trimesh = match shape.as_typed_shape() {
+        TypedShape::Ball(s) => s.to_trimesh(NSUB, NSUB / 2),
+        TypedShape::Cuboid(s) => s.to_trimesh(),
+        TypedShape::Capsule(s) => s.to_trimesh(NSUB, NSUB / 2),
+        TypedShape::Cylinder(s) => s.to_trimesh(NSUB),
+        TypedShape::Cone(s) => s.to_trimesh(NSUB),
+        TypedShape::ConvexPolyhedron(s) => s.to_trimesh(),
+        TypedShape::TriMesh(s) => (s.vertices().to_vec(), s.indices().to_vec()),
+        TypedShape::Compound(s) => {
+            let mut vertices = vec![];
+            let mut indices = vec![];
+            for (pos, part) in s.shapes() {
+                let (part_vertices, part_indices) = shape_trimesh(&**part)?;
+                let base = vertices.len() as u32;
+                vertices.extend(part_vertices.iter().map(|pt| pos * pt));
+                indices.extend(part_indices.iter().map(|idx| idx.map(|i| i + base)));
+            }
+            (vertices, indices)
+        }
+        _ => return None,
+    };
+
+    Some(trimesh)
+}
+
+/// A convex polygon, with its vertices in counterclockwise order.
+#[cfg(feature = "dim2")]
+type Polygon = Vec<Point<Real>>;
+
+/// Computes a boolean operation between the convex decompositions of two shapes.
+///
+/// The result is a set of disjoint convex polygons, expressed in the local-space of the
+/// first shape.
+#[cfg(feature = "dim2")]
+fn boolean_polygons(
+    op: BooleanOp,
+    pos1: &Isometry<Real>,
+    shape1: &dyn Shape,
+    pos2: &Isometry<Real>,
+    shape2: &dyn Shape,
+) -> anyhow::Result<Vec<Polygon>> {
+    let (Some(polygons1), Some(polygons2)) = (
+        shape_polygons(shape1, &Isometry::identity()),
+        shape_polygons(shape2, &pos1.inv_mul(pos2)),
+    ) else {
+        anyhow::bail!("unsupported shape type");
+    };
+
+    let result = match op {
+        BooleanOp::Union => {
+            let mut result = difference(polygons2, &polygons1);
+            result.extend(polygons1);
+            result
+        }
+        BooleanOp::Intersection => polygons1
+            .iter()
+            .flat_map(|p1| polygons2.iter().map(|p2| intersection(p1, p2)))
+            .filter(|polygon| is_valid(polygon))
+            .collect(),
+        BooleanOp::Difference => difference(polygons1, &polygons2),
+    };
+
+    Ok(result)
+}
+
+/// The convex polygons covering a shape, transformed by `pos`.
+#[cfg(feature = "dim2")]
+fn shape_polygons(shape: &dyn Shape, pos: &Isometry<Real>) -> Option<Vec<Polygon>> {
+    let polygons = match shape.as_typed_shape() {
+        TypedShape::Compound(s) => {
+            let mut polygons = vec![];
+            for (part_pos, part) in s.shapes() {
+                polygons.extend(shape_polygons(&**part, &(pos * part_pos))?);
+            }
+            polygons
+        }
+        TypedShape::TriMesh(s) => s
+            .triangles()
+            .map(|tri| counterclockwise([tri.a, tri.b, tri.c].iter().map(|pt| pos * pt).collect()))
+            .collect(),
+        _ => {
+            let points = convex_points(shape)?;
+            vec![counterclockwise(points.iter().map(|pt| pos * pt).collect())]
+        }
+    };
+
+    Some(polygons)
+}
+
+#[cfg(feature = "dim2")]
+fn signed_area(polygon: &[Point<Real>]) -> Real {
+    (0..polygon.len())
+        .map(|i| {
+            polygon[i]
+                .coords
+                .perp(&polygon[(i + 1) % polygon.len()].coords)
+        })
+        .sum()
+}
+
+#[cfg(feature = "dim2")]
+fn counterclockwise(mut polygon: Polygon) -> Polygon {
+    if signed_area(&polygon) < 0.0 {
+        polygon.reverse();
+    }
+    polygon
+}
+
+#[cfg(feature = "dim2")]
+fn is_valid(polygon: &[Point<Real>]) -> bool {
+    polygon.len() >= 3 && signed_area(polygon) > 1.0e-6
+}
+
+/// The outward normal of each edge of a polygon, with the offset of its supporting line.
+#[cfg(feature = "dim2")]
+fn edge_planes(polygon: &[Point<Real>]) -> impl Iterator<Item = (Vector<Real>, Real)> + '_ {
+    (0..polygon.len()).map(|i| {
+        let a = polygon[i];
+        let b = polygon[(i + 1) % polygon.len()];
+        let normal = Vector::new(b.y - a.y, a.x - b.x);
+        (normal, normal.dot(&a.coords))
+    })
+}
+
+/// Keeps the part of a convex polygon such that `normal.dot(x) <= bias`.
+#[cfg(feature = "dim2")]
+fn clip(polygon: &[Point<Real>], normal: &Vector<Real>, bias: Real) -> Polygon {
+    let mut result = vec![];
+
+    for i in 0..polygon.len() {
+        let a = polygon[i];
+        let b = polygon[(i + 1) % polygon.len()];
+        let da = normal.dot(&a.coords) - bias;
+        let db = normal.dot(&b.coords) - bias;
+
+        if da <= 0.0 {
+            result.push(a);
+        }
+
+        if (da < 0.0 && db > 0.0) || (da > 0.0 && db < 0.0) {
+            result.push(a + (b - a) * (da / (da - db)));
+        }
+    }
+
+    result
+}
+
+#[cfg(feature = "dim2")]
+fn intersection(polygon1: &[Point<Real>], polygon2: &[Point<Real>]) -> Polygon {
+    edge_planes(polygon2).fold(polygon1.to_vec(), |result, (normal, bias)| {
+        clip(&result, &normal, bias)
+    })
+}
+
+/// Subtracts a convex polygon from another one.
+///
+/// The result is split into convex pieces: the part of `polygon1` outside of the first edge
+/// of `polygon2`, then the part outside of the second edge but inside the first one, etc.
+#[cfg(feature = "dim2")]
+fn subtract(polygon1: &[Point<Real>], polygon2: &[Point<Real>]) -> Vec<Polygon> {
+    let mut remaining = polygon1.to_vec();
+    let mut pieces = vec![];
+
+    for (normal, bias) in edge_planes(polygon2) {
+        let outside = clip(&remaining, &-normal, -bias);
+        if is_valid(&outside) {
+            pieces.push(outside);
+        }
+
+        remaining = clip(&remaining, &normal, bias);
+        if !is_valid(&remaining) {
+            break;
+        }
+    }
+
+    pieces
+}
+
+#[cfg(feature = "dim2")]
+fn difference(polygons: Vec<Polygon>, holes: &[Polygon]) -> Vec<Polygon> {
+    holes.iter().fold(polygons, |pieces, hole| {
+        pieces
+            .iter()
+            .flat_map(|piece| subtract(piece, hole))
+            .collect()
+    })
+}
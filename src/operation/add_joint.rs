@@ -0,0 +1,179 @@
+use crate::operation::{Operation, Operations};
+use bevy::prelude::*;
+use bevy_rapier::math::{Rot, Vect};
+use bevy_rapier::prelude::*;
+
+/// The kind of joint created by the `AddJoint` tool.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum JointKind {
+    Fixed,
+    Revolute,
+    Prismatic,
+    #[cfg(feature = "dim3")]
+    Spherical,
+    Rope,
+    Spring,
+}
+
+impl JointKind {
+    /// Multibody joints don’t support the coupled axes of rope and spring joints.
+    pub fn supports_multibody(self) -> bool {
+        !matches!(self, Self::Rope | Self::Spring)
+    }
+}
+
+/// Default stiffness of the spring joints created by the `AddJoint` tool.
+const SPRING_STIFFNESS: f32 = 100.0;
+/// Default damping of the spring joints created by the `AddJoint` tool.
+const SPRING_DAMPING: f32 = 1.0;
+
+/// Attaches two bodies with a joint.
+///
+/// The joint frames are chosen so that the bodies stay in their current configuration: the
+/// anchors are the picked points, and the joint axis (for revolute and prismatic joints)
+/// is along the surface normal at the first anchor, or along the line joining both anchors
+/// for prismatic joints. Joints that keep their anchors together (fixed, revolute, and
+/// spherical joints) use the first picked point as the anchor of both bodies.
+pub fn add_joint(
+    mut commands: Commands,
+    operations: Res<Operations>,
+    transforms: Query<&GlobalTransform>,
+    multibody_joints: Query<(), With<MultibodyJoint>>,
+) {
+    for op in operations.iter() {
+        let Operation::AddJoint {
+            body1,
+            body2,
+            anchor1,
+            anchor2,
+            normal,
+            kind,
+            multibody,
+        } = op
+        else {
+            continue;
+        };
+
+        let (Ok(transform1), Ok(transform2)) = (transforms.get(*body1), transforms.get(*body2))
+        else {
+            warn!("Failed to add joint: one of the bodies no longer exists.");
+            continue;
+        };
+
+        let (pos1, rot1) = body_frame(transform1);
+        let (pos2, rot2) = body_frame(transform2);
+        let distance = (*anchor2 - *anchor1).length();
+
+        let mut joint: TypedJoint = match kind {
+            JointKind::Fixed => FixedJointBuilder::new().build().into(),
+            #[cfg(feature = "dim2")]
+            JointKind::Revolute => RevoluteJointBuilder::new().build().into(),
+            #[cfg(feature = "dim3")]
+            JointKind::Revolute => RevoluteJointBuilder::new(Vect::X).build().into(),
+            JointKind::Prismatic => PrismaticJointBuilder::new(Vect::X).build().into(),
+            #[cfg(feature = "dim3")]
+            JointKind::Spherical => SphericalJointBuilder::new().build().into(),
+            JointKind::Rope => RopeJointBuilder::new(distance).build().into(),
+            JointKind::Spring => {
+                SpringJointBuilder::new(distance, SPRING_STIFFNESS, SPRING_DAMPING)
+                    .build()
+                    .into()
+            }
+        };
+
+        // The joint axis is the local X axis of the joint frames.
+        let axis = match kind {
+            JointKind::Prismatic => (*anchor2 - *anchor1).try_normalize().unwrap_or(*normal),
+            _ => *normal,
+        };
+        let basis1 = basis_from_axis(inverse_rotate(rot1, axis));
+        let basis2 = relative_basis(rot1, rot2, basis1);
+
+        // Only the joints with a free translation between their anchors can keep them apart.
+        let anchor2 = match kind {
+            JointKind::Prismatic | JointKind::Rope | JointKind::Spring => *anchor2,
+            _ => *anchor1,
+        };
+
+        let data: &mut GenericJoint = joint.as_mut();
+        data.set_local_anchor1(inverse_rotate(rot1, *anchor1 - pos1))
+            .set_local_anchor2(inverse_rotate(rot2, anchor2 - pos2))
+            .set_local_basis1(basis1)
+            .set_local_basis2(basis2);
+
+        if *multibody && !kind.supports_multibody() {
+            warn!(
+                "{:?} joints can’t be multibody joints, adding an impulse joint instead.",
+                kind
+            );
+        }
+
+        if *multibody && kind.supports_multibody() {
+            if multibody_joints.get(*body2).is_ok() {
+                warn!("Failed to add joint: the second body already has a multibody joint.");
+                continue;
+            }
+            commands
+                .entity(*body2)
+                .insert(MultibodyJoint::new(*body1, joint));
+        } else {
+            commands.entity(*body2).with_children(|cmd| {
+                cmd.spawn(ImpulseJoint::new(*body1, joint))
+                    .insert(Name::new("Impulse joint"));
+            });
+        }
+    }
+}
+
+/// The world-space position and rotation of a body.
+#[cfg(feature = "dim2")]
+fn body_frame(transform: &GlobalTransform) -> (Vect, Rot) {
+    let transform = transform.compute_transform();
+    (
+        transform.translation.truncate(),
+        transform.rotation.to_scaled_axis().z,
+    )
+}
+
+/// The world-space position and rotation of a body.
+#[cfg(feature = "dim3")]
+fn body_frame(transform: &GlobalTransform) -> (Vect, Rot) {
+    let transform = transform.compute_transform();
+    (transform.translation, transform.rotation)
+}
+
+#[cfg(feature = "dim2")]
+fn inverse_rotate(rot: Rot, v: Vect) -> Vect {
+    Vec2::from_angle(-rot).rotate(v)
+}
+
+#[cfg(feature = "dim3")]
+fn inverse_rotate(rot: Rot, v: Vect) -> Vect {
+    rot.inverse() * v
+}
+
+/// The rotation mapping the X axis to `axis`.
+#[cfg(feature = "dim2")]
+fn basis_from_axis(axis: Vect) -> Rot {
+    axis.y.atan2(axis.x)
+}
+
+/// The rotation mapping the X axis to `axis`.
+#[cfg(feature = "dim3")]
+fn basis_from_axis(axis: Vect) -> Rot {
+    Quat::from_rotation_arc(Vect::X, axis.normalize())
+}
+
+/// The basis of the second joint frame matching the first one in the current configuration
+/// of the bodies.
+#[cfg(feature = "dim2")]
+fn relative_basis(rot1: Rot, rot2: Rot, basis1: Rot) -> Rot {
+    rot1 - rot2 + basis1
+}
+
+/// The basis of the second joint frame matching the first one in the current configuration
+/// of the bodies.
+#[cfg(feature = "dim3")]
+fn relative_basis(rot1: Rot, rot2: Rot, basis1: Rot) -> Rot {
+    rot2.inverse() * rot1 * basis1
+}
@@ -17,11 +17,23 @@ pub fn clear_scene(
     >,
 ) {
     for op in operations.iter() {
-        if let Operation::ClearScene = op {
-            progress.simulated_time = 0.0;
-            for entity in to_remove.iter() {
-                commands.entity(entity).despawn_recursive();
+        match op {
+            Operation::ClearScene => {
+                progress.simulated_time = 0.0;
+                progress.simulated_steps = 0;
+                for entity in to_remove.iter() {
+                    commands.entity(entity).despawn_recursive();
+                }
             }
+            Operation::Despawn(entities) => {
+                for entity in entities {
+                    // The entity may have been despawned with one of its ancestors already.
+                    if let Some(entity) = commands.get_entity(*entity) {
+                        entity.despawn_recursive();
+                    }
+                }
+            }
+            _ => {}
         }
     }
 }
@@ -0,0 +1,347 @@
+use crate::operation::{Operation, Operations};
+use crate::render::ColliderRender;
+use crate::utils::{ColliderBundle, ColliderRenderBundle, RigidBodyBundle};
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy_rapier::parry::mass_properties::MassProperties;
+use bevy_rapier::parry::shape::{Shape, SharedShape, TypedShape};
+use bevy_rapier::prelude::*;
+use bevy_rapier::rapier::geometry::Collider as RapierCollider;
+use bevy_rapier::rapier::math::{Isometry, Point, Vector};
+
+#[cfg(feature = "dim3")]
+use {bevy_rapier::parry::query::SplitResult, bevy_rapier::rapier::na::Unit};
+
+/// Number of subdivisions used for approximating curved shapes before clipping them.
+const NSUB: u32 = 16;
+
+/// A collider of a body being cut: one of its pieces, or the whole collider if the plane
+/// doesn’t cross it.
+struct CutPart<'a> {
+    collider: &'a RapierCollider,
+    entity: Entity,
+    shape: SharedShape,
+    whole: bool,
+}
+
+/// Splits every body touched by the cutting blade into two bodies, on each side of the
+/// cutting plane.
+///
+/// Each collider of a body is split by the plane, and the colliders it doesn’t cross are
+/// kept whole by the body on their side.
+pub fn cut(
+    mut commands: Commands,
+    operations: Res<Operations>,
+    context: Res<RapierContext>,
+    colliders: Query<(Option<&ColliderRender>, Option<&Name>)>,
+) {
+    for op in operations.iter() {
+        let Operation::Cut {
+            point,
+            normal,
+            blade,
+        } = op
+        else {
+            continue;
+        };
+
+        let mut touched = vec![];
+        for part in blade {
+            context.intersections_with_shape(
+                Vect::ZERO,
+                Rot::default(),
+                part,
+                QueryFilter::default(),
+                |entity| {
+                    touched.push(context.collider_parent(entity).unwrap_or(entity));
+                    true
+                },
+            );
+        }
+        touched.sort();
+        touched.dedup();
+
+        let normal = Vector::from(*normal);
+        let plane_bias = normal.dot(&Vector::from(*point));
+
+        for entity in touched {
+            let Some(rb) = context
+                .entity2body()
+                .get(&entity)
+                .and_then(|h| context.bodies.get(*h))
+            else {
+                continue;
+            };
+
+            let mut sides: [Vec<CutPart>; 2] = [vec![], vec![]];
+            let mut crossed = false;
+
+            for handle in rb.colliders() {
+                let (Some(co), Some(co_entity)) = (
+                    context.colliders.get(*handle),
+                    context.collider_entity(*handle),
+                ) else {
+                    continue;
+                };
+
+                // Express the plane in the local-space of the collider.
+                let local_normal = co.position().inverse_transform_vector(&normal);
+                let bias = plane_bias - normal.dot(&co.position().translation.vector);
+
+                if let Some((negative, positive)) = split_shape(co.shape(), &local_normal, bias) {
+                    for (side, shape) in sides.iter_mut().zip([negative, positive]) {
+                        side.push(CutPart {
+                            collider: co,
+                            entity: co_entity,
+                            shape,
+                            whole: false,
+                        });
+                    }
+                    crossed = true;
+                } else {
+                    let center = co.compute_aabb().center();
+                    let side = usize::from(normal.dot(&center.coords) >= plane_bias);
+                    sides[side].push(CutPart {
+                        collider: co,
+                        entity: co_entity,
+                        shape: co.shared_shape().clone(),
+                        whole: true,
+                    });
+                }
+            }
+
+            if !crossed {
+                continue;
+            }
+
+            let body = RigidBodyBundle::from(rb);
+            let body_com = Vect::from(rb.center_of_mass().coords);
+            let body_name = colliders.get(entity).ok().and_then(|(_, name)| name);
+
+            for parts in sides {
+                // The pieces move like the part of the original body they were cut from.
+                let mass_properties = parts.iter().fold(MassProperties::zero(), |acc, part| {
+                    acc + part
+                        .shape
+                        .mass_properties(1.0)
+                        .transform_by(part.collider.position())
+                });
+                let piece_com = Vect::from(mass_properties.local_com.coords);
+                let velocity = Velocity {
+                    linvel: body.velocity.linear_velocity_at_point(piece_com, body_com),
+                    angvel: body.velocity.angvel,
+                };
+
+                let rigid_body = RigidBodyBundle {
+                    velocity,
+                    additional_mass_properties: AdditionalMassProperties::default(),
+                    ..body
+                };
+
+                let mut piece_entity = commands.spawn(rigid_body);
+                piece_entity.insert(TransformBundle::from_transform(
+                    bevy_rapier::utils::iso_to_transform(rb.position()),
+                ));
+                if let Some(name) = body_name {
+                    piece_entity.insert(name.clone());
+                }
+
+                // Like the bodies created with the insertion tools, a single collider at the
+                // origin of the body is attached to the body entity, and the other colliders
+                // are child entities.
+                match &parts[..] {
+                    [part] if *part.collider.position() == *rb.position() => {
+                        insert_part(&mut piece_entity, part, &colliders, None);
+                    }
+                    _ => {
+                        piece_entity
+                            .insert(VisibilityBundle::default())
+                            .with_children(|cmd| {
+                                for part in &parts {
+                                    let mut child = cmd.spawn_empty();
+                                    insert_part(&mut child, part, &colliders, Some(rb.position()));
+                                }
+                            });
+                    }
+                }
+            }
+
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Inserts the collider of a cut body, positioned relative to the body at `body_pos` if the
+/// collider is on a child entity of the body.
+///
+/// The mass properties of the pieces are recomputed from the density and the new shape.
+fn insert_part(
+    entity: &mut EntityCommands,
+    part: &CutPart,
+    colliders: &Query<(Option<&ColliderRender>, Option<&Name>)>,
+    body_pos: Option<&Isometry<Real>>,
+) {
+    let co = part.collider;
+    let mut collider = ColliderBundle {
+        collider: Collider::from(part.shape.clone()),
+        ..ColliderBundle::from(co)
+    };
+    if !part.whole {
+        collider.mass_properties = ColliderMassProperties::Density(co.density());
+    }
+
+    let (render, name) = colliders.get(part.entity).unwrap_or_default();
+    let color = render.map(|r| r.color).unwrap_or_default();
+
+    entity
+        .insert(collider)
+        .insert(ColliderRenderBundle::with_color(color));
+    if let Some(body_pos) = body_pos {
+        entity.insert(TransformBundle::from_transform(
+            bevy_rapier::utils::iso_to_transform(&body_pos.inv_mul(co.position())),
+        ));
+    }
+    if let Some(name) = name {
+        entity.insert(name.clone());
+    }
+    if co.is_sensor() {
+        entity.insert(Sensor);
+    }
+}
+
+/// Splits a shape by the plane with the given `normal` and passing through `normal * bias`,
+/// both expressed in the local-space of the shape.
+///
+/// Returns the pieces on the negative and positive side of the plane, or `None` if the plane
+/// doesn’t cross the shape, or if this kind of shape can’t be split.
+fn split_shape(
+    shape: &dyn Shape,
+    normal: &Vector<Real>,
+    bias: Real,
+) -> Option<(SharedShape, SharedShape)> {
+    match shape.as_typed_shape() {
+        TypedShape::Compound(s) => split_parts(s.shapes(), normal, bias),
+        #[cfg(feature = "dim2")]
+        TypedShape::TriMesh(s) => {
+            let triangles: Vec<_> = s
+                .triangles()
+                .map(|tri| (Isometry::identity(), SharedShape::new(tri)))
+                .collect();
+            split_parts(&triangles, normal, bias)
+        }
+        #[cfg(feature = "dim3")]
+        TypedShape::TriMesh(s) => {
+            match s.local_split(&Unit::new_normalize(*normal), bias, 1.0e-5) {
+                SplitResult::Pair(negative, positive) => {
+                    Some((SharedShape::new(negative), SharedShape::new(positive)))
+                }
+                _ => None,
+            }
+        }
+        _ => {
+            let points = convex_points(shape)?;
+            split_convex(&points, normal, bias)
+        }
+    }
+}
+
+/// Splits each part of a compound shape, and gathers the resulting pieces on each side of
+/// the plane.
+fn split_parts(
+    parts: &[(Isometry<Real>, SharedShape)],
+    normal: &Vector<Real>,
+    bias: Real,
+) -> Option<(SharedShape, SharedShape)> {
+    let mut negative = vec![];
+    let mut positive = vec![];
+
+    for (pos, part) in parts {
+        let local_normal = pos.inverse_transform_vector(normal);
+        let local_bias = bias - normal.dot(&pos.translation.vector);
+
+        if let Some((part_negative, part_positive)) =
+            split_shape(&**part, &local_normal, local_bias)
+        {
+            negative.push((*pos, part_negative));
+            positive.push((*pos, part_positive));
+        } else {
+            // The part isn’t crossed by the plane (or can’t be split), keep it whole.
+            let center = part.compute_aabb(pos).center();
+            if normal.dot(&center.coords) < bias {
+                negative.push((*pos, part.clone()));
+            } else {
+                positive.push((*pos, part.clone()));
+            }
+        }
+    }
+
+    if negative.is_empty() || positive.is_empty() {
+        return None;
+    }
+
+    Some((
+        SharedShape::compound(negative),
+        SharedShape::compound(positive),
+    ))
+}
+
+/// Clips the convex hull of the given points.
+fn split_convex(
+    points: &[Point<Real>],
+    normal: &Vector<Real>,
+    bias: Real,
+) -> Option<(SharedShape, SharedShape)> {
+    let dist = |pt: &Point<Real>| normal.dot(&pt.coords) - bias;
+    let (mut negative, mut positive): (Vec<_>, Vec<_>) =
+        points.iter().copied().partition(|pt| dist(pt) < 0.0);
+
+    if negative.is_empty() || positive.is_empty() {
+        return None;
+    }
+
+    // The section of the hull is the convex hull of the intersections between the plane
+    // and the segments joining points on both sides.
+    let mut section = vec![];
+    for a in &negative {
+        for b in &positive {
+            let (da, db) = (dist(a), dist(b));
+            section.push(a + (b - a) * (da / (da - db)));
+        }
+    }
+
+    negative.extend_from_slice(&section);
+    positive.extend_from_slice(&section);
+    Some((
+        SharedShape::convex_hull(&negative)?,
+        SharedShape::convex_hull(&positive)?,
+    ))
+}
+
+/// The vertices of a convex shape, or of its approximation for curved shapes.
+#[cfg(feature = "dim2")]
+pub(super) fn convex_points(shape: &dyn Shape) -> Option<Vec<Point<Real>>> {
+    let points = match shape.as_typed_shape() {
+        TypedShape::Ball(s) => s.to_polyline(NSUB),
+        TypedShape::Cuboid(s) => s.to_polyline(),
+        TypedShape::Capsule(s) => s.to_polyline(NSUB),
+        TypedShape::Triangle(s) => s.vertices().to_vec(),
+        TypedShape::ConvexPolygon(s) => s.points().to_vec(),
+        _ => return None,
+    };
+    Some(points)
+}
+
+/// The vertices of a convex shape, or of its approximation for curved shapes.
+#[cfg(feature = "dim3")]
+pub(super) fn convex_points(shape: &dyn Shape) -> Option<Vec<Point<Real>>> {
+    let (points, _) = match shape.as_typed_shape() {
+        TypedShape::Ball(s) => s.to_trimesh(NSUB, NSUB / 2),
+        TypedShape::Cuboid(s) => s.to_trimesh(),
+        TypedShape::Capsule(s) => s.to_trimesh(NSUB, NSUB / 2),
+        TypedShape::Cylinder(s) => s.to_trimesh(NSUB),
+        TypedShape::Cone(s) => s.to_trimesh(NSUB),
+        TypedShape::ConvexPolyhedron(s) => s.to_trimesh(),
+        _ => return None,
+    };
+    Some(points)
+}
@@ -0,0 +1,22 @@
+use crate::operation::{Operation, Operations};
+use bevy::prelude::*;
+use bevy_rapier::prelude::*;
+use std::path::Path;
+
+pub fn export_scene(operations: Res<Operations>, context: Res<RapierContext>) {
+    for op in operations.iter() {
+        if let Operation::ExportScene(path) = op {
+            match write_scene(&context, path) {
+                Ok(()) => info!("Scene exported to {}", path.display()),
+                Err(e) => error!("Failed to export scene: {:?}", e),
+            }
+        }
+    }
+}
+
+/// Writes the scene as JSON, in the same format as the one read by the “Open…” menu.
+fn write_scene(context: &RapierContext, path: &Path) -> anyhow::Result<()> {
+    let data = serde_json::to_vec(context)?;
+    std::fs::write(path, data)?;
+    Ok(())
+}
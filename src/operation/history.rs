@@ -0,0 +1,238 @@
+use crate::control::CharacterControlOptions;
+use crate::operation::{Operation, Operations};
+use crate::render::{ColliderRender, ColliderRenderMesh};
+use crate::PhysicsProgress;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_rapier::control::KinematicCharacterController;
+use bevy_rapier::plugin::RapierContext;
+use bevy_rapier::prelude::Real;
+use bevy_rapier::rapier::dynamics::ImpulseJointHandle;
+use std::collections::{HashMap, VecDeque};
+
+pub const DEFAULT_HISTORY_LEN: usize = 50;
+
+/// The components of the scene entities that aren’t part of the physics context.
+#[derive(SystemParam)]
+pub struct SnapshotComponents<'w, 's> {
+    renders: Query<'w, 's, &'static ColliderRender>,
+    render_meshes: Query<'w, 's, &'static ColliderRenderMesh>,
+    names: Query<'w, 's, &'static Name>,
+    character_controllers: Query<
+        'w,
+        's,
+        (
+            &'static KinematicCharacterController,
+            &'static CharacterControlOptions,
+        ),
+    >,
+}
+
+/// The components of a body or collider entity saved with a snapshot.
+#[derive(Clone, Default)]
+pub struct SavedComponents {
+    pub name: Option<Name>,
+    pub color: Option<Color>,
+    pub render_mesh: Option<ColliderRenderMesh>,
+    pub character_controller: Option<(KinematicCharacterController, CharacterControlOptions)>,
+}
+
+/// A copy of the physics scene and of the simulation progress at some point in time.
+#[derive(Clone)]
+pub struct SceneSnapshot {
+    data: Vec<u8>,
+    /// The components of the bodies and colliders, identified by the `user_data` of their
+    /// Rapier object (the bits of their entity).
+    components: HashMap<u128, SavedComponents>,
+    joint_names: HashMap<ImpulseJointHandle, Name>,
+    pub simulated_time: Real,
+    pub simulated_steps: usize,
+}
+
+impl SceneSnapshot {
+    pub fn new(
+        context: &RapierContext,
+        progress: &PhysicsProgress,
+        components: &SnapshotComponents,
+    ) -> anyhow::Result<Self> {
+        // Keep track of the components that can’t be deduced from the physics context, so
+        // they don’t change when the scene is restored.
+        let entities = context
+            .entity2body()
+            .keys()
+            .chain(context.entity2collider().keys());
+        let saved = entities
+            .map(|entity| {
+                let saved = SavedComponents {
+                    name: components.names.get(*entity).ok().cloned(),
+                    color: components.renders.get(*entity).ok().map(|r| r.color),
+                    render_mesh: components.render_meshes.get(*entity).ok().cloned(),
+                    character_controller: components
+                        .character_controllers
+                        .get(*entity)
+                        .ok()
+                        .map(|(controller, options)| (controller.clone(), *options)),
+                };
+                (entity.to_bits() as u128, saved)
+            })
+            .collect();
+        let joint_names = context
+            .entity2impulse_joint()
+            .iter()
+            .filter_map(|(entity, handle)| Some((*handle, components.names.get(*entity).ok()?)))
+            .map(|(handle, name)| (handle, name.clone()))
+            .collect();
+
+        Ok(Self {
+            data: bincode::serialize(context)?,
+            components: saved,
+            joint_names,
+            simulated_time: progress.simulated_time,
+            simulated_steps: progress.simulated_steps,
+        })
+    }
+
+    pub fn context(&self) -> anyhow::Result<RapierContext> {
+        Ok(bincode::deserialize(&self.data)?)
+    }
+
+    /// The saved components of the body or collider with the given `user_data`.
+    pub fn components(&self, user_data: u128) -> Option<&SavedComponents> {
+        self.components.get(&user_data)
+    }
+
+    pub fn joint_name(&self, handle: ImpulseJointHandle) -> Option<&Name> {
+        self.joint_names.get(&handle)
+    }
+}
+
+/// The undo/redo history of the scene edits.
+///
+/// Edits are recorded automatically for every operation pushed to the `Operations` queue.
+/// Edits that don’t go through an operation (inspector, gizmo) must call `checkpoint` before
+/// modifying the scene. Deletions must go through `Operation::Despawn`, so the components of
+/// the deleted entities are saved before they are despawned.
+#[derive(Resource)]
+pub struct SceneHistory {
+    undo_stack: VecDeque<SceneSnapshot>,
+    redo_stack: Vec<SceneSnapshot>,
+    max_len: usize,
+    checkpoint_requested: bool,
+    undo_requested: bool,
+    redo_requested: bool,
+}
+
+impl Default for SceneHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_LEN)
+    }
+}
+
+impl SceneHistory {
+    pub fn new(max_len: usize) -> Self {
+        Self {
+            undo_stack: VecDeque::new(),
+            redo_stack: vec![],
+            max_len,
+            checkpoint_requested: false,
+            undo_requested: false,
+            redo_requested: false,
+        }
+    }
+
+    /// Requests the current scene to be saved before it gets modified.
+    pub fn checkpoint(&mut self) {
+        self.checkpoint_requested = true;
+    }
+
+    pub fn undo(&mut self) {
+        self.undo_requested = true;
+    }
+
+    pub fn redo(&mut self) {
+        self.redo_requested = true;
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    fn save(
+        &mut self,
+        context: &RapierContext,
+        progress: &PhysicsProgress,
+        components: &SnapshotComponents,
+    ) {
+        match SceneSnapshot::new(context, progress, components) {
+            Ok(snapshot) => {
+                self.push_undo(snapshot);
+                self.redo_stack.clear();
+            }
+            Err(e) => error!("Failed to save scene history: {:?}", e),
+        }
+    }
+
+    fn push_undo(&mut self, snapshot: SceneSnapshot) {
+        self.undo_stack.push_back(snapshot);
+        while self.undo_stack.len() > self.max_len {
+            self.undo_stack.pop_front();
+        }
+    }
+}
+
+/// Saves the scene before it gets edited, and restores it on undo/redo.
+///
+/// This must run before the operations are processed, and before the edits are
+/// synchronized with the physics context.
+pub fn record_history(
+    mut history: ResMut<SceneHistory>,
+    mut operations: ResMut<Operations>,
+    context: Res<RapierContext>,
+    progress: Res<PhysicsProgress>,
+    components: SnapshotComponents,
+) {
+    let undo = std::mem::take(&mut history.undo_requested);
+    let redo = std::mem::take(&mut history.redo_requested);
+    let checkpoint = std::mem::take(&mut history.checkpoint_requested);
+
+    let restoring = operations
+        .iter()
+        .any(|op| matches!(op, Operation::RestoreScene(_)));
+    let edited = operations.iter().any(|op| op.is_scene_edit());
+
+    if restoring {
+        return;
+    }
+
+    let snapshot = || match SceneSnapshot::new(&context, &progress, &components) {
+        Ok(snapshot) => Some(snapshot),
+        Err(e) => {
+            error!("Failed to save scene history: {:?}", e);
+            None
+        }
+    };
+
+    if undo {
+        if let Some(previous) = history.undo_stack.pop_back() {
+            if let Some(current) = snapshot() {
+                history.redo_stack.push(current);
+            }
+            operations.push(Operation::ClearScene);
+            operations.push(Operation::RestoreScene(previous));
+        }
+    } else if redo {
+        if let Some(next) = history.redo_stack.pop() {
+            if let Some(current) = snapshot() {
+                history.push_undo(current);
+            }
+            operations.push(Operation::ClearScene);
+            operations.push(Operation::RestoreScene(next));
+        }
+    } else if checkpoint || edited {
+        history.save(&context, &progress, &components);
+    }
+}
@@ -1,23 +1,185 @@
 use crate::operation::{Operation, Operations};
-use crate::utils::{ColliderRenderBundle, RigidBodyBundle};
+use crate::render::ColliderRenderMesh;
+use crate::styling::ColorGenerator;
+use crate::utils::{ColliderBundle, ColliderRenderBundle, RigidBodyBundle};
+use crate::MainCamera;
+use bevy::asset::LoadState;
 use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology, VertexAttributeValues};
+use bevy::render::render_asset::RenderAssetUsages;
 use bevy_rapier::prelude::*;
+use bevy_rapier::rapier::math::Isometry;
+use std::path::Path;
+
+/// Distance between the camera and the imported mesh, in addition to the mesh size.
+const IMPORT_DISTANCE: f32 = 2.0;
+
+pub struct PendingMeshImport {
+    name: String,
+    mesh: Handle<Mesh>,
+    shape: ComputedColliderShape,
+    preview: bool,
+}
+
+/// The collider of a mesh being imported, displayed before the import is confirmed.
+#[derive(Resource, Default)]
+pub struct MeshImportPreview {
+    collider: Option<Collider>,
+    transform: Transform,
+}
 
 pub fn import_mesh(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     operations: Res<Operations>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut colors: ResMut<ColorGenerator>,
+    mut preview: ResMut<MeshImportPreview>,
+    mut pending: Local<Vec<PendingMeshImport>>,
+    cameras: Query<&GlobalTransform, With<MainCamera>>,
 ) {
     let operations = &*operations;
     for op in operations.iter() {
-        if let Operation::ImportMesh(path, shape) = op {
-            // let handle: Handle<Mesh> = asset_server.load(path.as_path());
-            // commands
-            //     .spawn(AsyncCollider(shape.clone()))
-            //     .insert(handle)
-            //     .insert(TransformBundle::default())
-            //     .insert(RigidBodyBundle::fixed())
-            //     .insert(ColliderRenderBundle::default());
+        let (path, shape, is_preview) = match op {
+            Operation::ImportMesh(path, shape) => (path, shape, false),
+            Operation::PreviewMesh(path, shape) => (path, shape, true),
+            Operation::ClearMeshPreview => {
+                preview.collider = None;
+                pending.retain(|import| !import.preview);
+                continue;
+            }
+            _ => continue,
+        };
+
+        // Only the latest preview is relevant.
+        preview.collider = None;
+        pending.retain(|import| !import.preview);
+
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "Mesh".to_string());
+
+        // OBJ files are handled by the asset server, but there is no loader for STL.
+        let mesh = if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("stl"))
+        {
+            match load_stl(path) {
+                Ok(mesh) => meshes.add(mesh),
+                Err(e) => {
+                    error!("Failed to import mesh {}: {:?}", path.display(), e);
+                    continue;
+                }
+            }
+        } else {
+            asset_server.load(path.clone())
+        };
+
+        pending.push(PendingMeshImport {
+            name,
+            mesh,
+            shape: shape.clone(),
+            preview: is_preview,
+        });
+    }
+
+    pending.retain(|import| {
+        if let Some(LoadState::Failed(e)) = asset_server.get_load_state(&import.mesh) {
+            error!("Failed to import mesh {}: {:?}", import.name, e);
+            return false;
+        }
+
+        let Some(mesh) = meshes.get_mut(&import.mesh) else {
+            // Still loading.
+            return true;
+        };
+
+        // Center the mesh so the body origin (and its gizmo) lies in the middle of it.
+        let Some(aabb) = mesh.compute_aabb() else {
+            error!("Failed to import mesh {}: it has no vertices", import.name);
+            return false;
+        };
+        mesh.translate_by(-Vec3::from(aabb.center));
+
+        if mesh.indices().is_none() {
+            let indices = (0..mesh.count_vertices() as u32).collect();
+            mesh.insert_indices(Indices::U32(indices));
+        }
+
+        let Some(collider) = Collider::from_bevy_mesh(mesh, &import.shape) else {
+            error!(
+                "Failed to import mesh {}: unable to compute its collider",
+                import.name
+            );
+            return false;
+        };
+
+        let mut transform = Transform::default();
+        if let Ok(camera) = cameras.get_single() {
+            let distance = Vec3::from(aabb.half_extents).length() * 2.0 + IMPORT_DISTANCE;
+            transform.translation = camera.translation() + camera.forward() * distance;
+        }
+
+        if import.preview {
+            preview.collider = Some(collider);
+            preview.transform = transform;
+            return false;
+        }
+
+        // Triangle meshes have no volume, so they can only be used for fixed bodies.
+        let rigid_body = match import.shape {
+            ComputedColliderShape::TriMesh => RigidBodyBundle::fixed(),
+            _ => RigidBodyBundle::dynamic(),
+        };
+
+        commands
+            .spawn(ColliderBundle::new(collider))
+            .insert(rigid_body)
+            .insert(Name::new(import.name.clone()))
+            .insert(TransformBundle::from_transform(transform))
+            .insert(ColliderRenderBundle::new(&mut colors))
+            .insert(ColliderRenderMesh(import.mesh.clone()));
+
+        false
+    });
+}
+
+/// Draws the edges of each convex part of the previewed mesh collider, with one color per part.
+pub fn draw_mesh_import_preview(
+    mut gizmos: Gizmos,
+    mut colors: ResMut<ColorGenerator>,
+    preview: Res<MeshImportPreview>,
+) {
+    let Some(collider) = &preview.collider else {
+        return;
+    };
+
+    let parts: Vec<_> = match collider.raw.as_compound() {
+        Some(compound) => compound
+            .shapes()
+            .iter()
+            .map(|(pos, shape)| (*pos, shape.as_ref()))
+            .collect(),
+        None => vec![(Isometry::identity(), collider.raw.as_ref())],
+    };
+
+    for (i, (pos, shape)) in parts.into_iter().enumerate() {
+        let (vertices, indices) = if let Some(hull) = shape.as_convex_polyhedron() {
+            hull.to_trimesh()
+        } else if let Some(trimesh) = shape.as_trimesh() {
+            (trimesh.vertices().to_vec(), trimesh.indices().to_vec())
+        } else {
+            continue;
+        };
+
+        let color = colors.gen_region_color(i);
+        for triangle in indices {
+            let points = [0, 1, 2, 0].map(|k| {
+                let pt = pos * vertices[triangle[k] as usize];
+                preview.transform * Vec3::new(pt.x, pt.y, pt.z)
+            });
+            gizmos.linestrip(points, color);
         }
     }
 }
@@ -34,3 +196,68 @@ pub fn set_trimesh_flags(_changed_shapes: Query<&mut Collider, Changed<Collider>
     //     }
     // }
 }
+
+/// Reads an ASCII or binary STL file.
+fn load_stl(path: &Path) -> anyhow::Result<Mesh> {
+    let data = std::fs::read(path)?;
+
+    // ASCII files start with "solid", but some binary files do too, so check the
+    // binary file size first.
+    let is_binary = data.len() >= 84 && {
+        let num_triangles = u32::from_le_bytes([data[80], data[81], data[82], data[83]]);
+        data.len() == 84 + num_triangles as usize * 50
+    };
+
+    let vertices: Vec<[f32; 3]> = if is_binary {
+        data[84..]
+            .chunks_exact(50)
+            .flat_map(|triangle| {
+                // Skip the normal, and the attribute byte count at the end.
+                triangle[12..48].chunks_exact(12).map(|vertex| {
+                    let coord = |i: usize| {
+                        f32::from_le_bytes([
+                            vertex[i * 4],
+                            vertex[i * 4 + 1],
+                            vertex[i * 4 + 2],
+                            vertex[i * 4 + 3],
+                        ])
+                    };
+                    [coord(0), coord(1), coord(2)]
+                })
+            })
+            .collect()
+    } else {
+        let text = std::str::from_utf8(&data)?;
+        let mut vertices = vec![];
+        for line in text.lines() {
+            let mut words = line.split_whitespace();
+            if words.next() == Some("vertex") {
+                let coords: Vec<f32> = words.map(str::parse).collect::<Result<_, _>>()?;
+                anyhow::ensure!(coords.len() == 3, "invalid vertex: {}", line);
+                vertices.push([coords[0], coords[1], coords[2]]);
+            }
+        }
+        vertices
+    };
+
+    anyhow::ensure!(
+        !vertices.is_empty() && vertices.len() % 3 == 0,
+        "invalid number of vertices: {}",
+        vertices.len()
+    );
+
+    let indices = (0..vertices.len() as u32).collect();
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    );
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_POSITION,
+        VertexAttributeValues::from(vertices),
+    );
+    // Each triangle has its own vertices, so the normals can be computed before
+    // adding the indices needed for building the collider.
+    mesh.compute_flat_normals();
+    mesh.insert_indices(Indices::U32(indices));
+    Ok(mesh)
+}
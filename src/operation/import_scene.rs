@@ -1,18 +1,218 @@
-use crate::operation::{Operation, Operations};
+use crate::operation::history::SavedComponents;
+use crate::operation::{Operation, Operations, SceneSnapshot};
 use crate::styling::ColorGenerator;
 use crate::utils::{ColliderBundle, ColliderRenderBundle, RigidBodyBundle};
+use crate::PhysicsProgress;
 use bevy::prelude::*;
+use bevy_rapier::control::KinematicCharacterControllerOutput;
 use bevy_rapier::prelude::*;
+use bevy_rapier::rapier::geometry::Collider as RapierCollider;
+use bevy_rapier::rapier::math::Isometry;
 use std::collections::HashMap;
 
 pub fn import_scene(
     mut commands: Commands,
     operations: Res<Operations>,
     mut colors: ResMut<ColorGenerator>,
+    mut progress: ResMut<PhysicsProgress>,
 ) {
     for op in operations.iter() {
-        if let Operation::ImportScene(scene) = op {
-            todo!()
+        match op {
+            Operation::ImportScene(scene) => {
+                spawn_scene(&mut commands, scene, &mut colors, None);
+            }
+            Operation::RestoreScene(snapshot) => match snapshot.context() {
+                Ok(scene) => {
+                    progress.simulated_time = snapshot.simulated_time;
+                    progress.simulated_steps = snapshot.simulated_steps;
+                    spawn_scene(&mut commands, &scene, &mut colors, Some(snapshot));
+                }
+                Err(e) => error!("Failed to restore scene: {:?}", e),
+            },
+            _ => {}
         }
     }
 }
+
+/// Spawns the entities for every body, collider, and joint of `scene`.
+///
+/// When restoring a `snapshot`, the entities get back the components saved with it (names,
+/// colors, render meshes, and character controllers), and each collider is spawned on the
+/// body entity, or on a child entity, like it was when the snapshot was taken.
+fn spawn_scene(
+    commands: &mut Commands,
+    scene: &RapierContext,
+    colors: &mut ColorGenerator,
+    snapshot: Option<&SceneSnapshot>,
+) {
+    let saved = |user_data: u128| snapshot.and_then(|s| s.components(user_data));
+    let collider_render = |co: &RapierCollider, default: &ColliderRenderBundle| {
+        saved(co.user_data)
+            .and_then(|saved| saved.color)
+            .map(ColliderRenderBundle::with_color)
+            .unwrap_or_else(|| default.clone())
+    };
+
+    let mut body2entity = HashMap::new();
+
+    for (handle, body) in scene.bodies.iter() {
+        let render = ColliderRenderBundle::new(colors);
+        let saved_body = saved(body.user_data);
+        let mut entity = commands.spawn(RigidBodyBundle::from(body));
+        entity
+            .insert(
+                saved_body
+                    .and_then(|saved| saved.name.clone())
+                    .unwrap_or_else(|| Name::new("Rigid body")),
+            )
+            .insert(TransformBundle::from_transform(
+                bevy_rapier::utils::iso_to_transform(body.position()),
+            ))
+            .insert(VisibilityBundle::default());
+
+        if !body.is_enabled() {
+            entity.insert(RigidBodyDisabled);
+        }
+        insert_character_controller(&mut entity, saved_body);
+
+        // A restored collider goes back to the entity it was on. Otherwise, if the body
+        // only has a single collider sitting at its origin, attach it to the same entity
+        // so it can be selected and moved like the bodies created with the insertion
+        // tools. The other colliders are child entities positioned relative to the body.
+        let on_body_entity = |co: &RapierCollider| match snapshot {
+            Some(_) => co.user_data == body.user_data,
+            None => {
+                body.colliders().len() == 1
+                    && co
+                        .position_wrt_parent()
+                        .map(|pos| *pos == Isometry::identity())
+                        .unwrap_or(true)
+            }
+        };
+
+        for co_handle in body.colliders() {
+            let Some(collider) = scene.colliders.get(*co_handle) else {
+                continue;
+            };
+
+            if on_body_entity(collider) {
+                entity
+                    .insert(ColliderBundle::from(collider))
+                    .insert(collider_render(collider, &render));
+                insert_collider_components(&mut entity, collider, saved(collider.user_data));
+            } else {
+                entity.with_children(|cmd| {
+                    let local_pos = collider
+                        .position_wrt_parent()
+                        .copied()
+                        .unwrap_or_else(Isometry::identity);
+                    let saved_collider = saved(collider.user_data);
+                    let mut child = cmd.spawn(ColliderBundle::from(collider));
+                    child
+                        .insert(
+                            saved_collider
+                                .and_then(|saved| saved.name.clone())
+                                .unwrap_or_else(|| Name::new("Collision Shape")),
+                        )
+                        .insert(TransformBundle::from_transform(
+                            bevy_rapier::utils::iso_to_transform(&local_pos),
+                        ))
+                        .insert(collider_render(collider, &render));
+                    insert_collider_components(&mut child, collider, saved_collider);
+                });
+            }
+        }
+
+        body2entity.insert(handle, entity.id());
+    }
+
+    // Colliders that aren’t attached to any rigid-body.
+    for (_, collider) in scene.colliders.iter() {
+        if collider.parent().is_none() {
+            let saved_collider = saved(collider.user_data);
+            let render = ColliderRenderBundle::new(colors);
+            let mut entity = commands.spawn(ColliderBundle::from(collider));
+            entity
+                .insert(
+                    saved_collider
+                        .and_then(|saved| saved.name.clone())
+                        .unwrap_or_else(|| Name::new("Collision Shape")),
+                )
+                .insert(TransformBundle::from_transform(
+                    bevy_rapier::utils::iso_to_transform(collider.position()),
+                ))
+                .insert(collider_render(collider, &render));
+            insert_collider_components(&mut entity, collider, saved_collider);
+            insert_character_controller(&mut entity, saved_collider);
+        }
+    }
+
+    // A rigid-body can be the second endpoint of several impulse joints, so
+    // each joint lives in its own child entity of that rigid-body.
+    for (joint_handle, joint) in scene.impulse_joints.iter() {
+        if let (Some(entity1), Some(entity2)) =
+            (body2entity.get(&joint.body1), body2entity.get(&joint.body2))
+        {
+            let name = snapshot
+                .and_then(|s| s.joint_name(joint_handle))
+                .cloned()
+                .unwrap_or_else(|| Name::new("Impulse joint"));
+            let joint = ImpulseJoint::new(*entity1, GenericJoint { raw: joint.data });
+            commands.entity(*entity2).with_children(|cmd| {
+                cmd.spawn(joint).insert(name);
+            });
+        }
+    }
+
+    // A rigid-body has at most one parent in a multibody, so the joint can
+    // be attached directly to its entity.
+    for (_, _, multibody, link) in scene.multibody_joints.iter() {
+        let parent = link
+            .parent_id()
+            .and_then(|id| multibody.link(id))
+            .map(|parent| parent.rigid_body_handle());
+
+        if let Some((entity1, entity2)) = parent.and_then(|parent| {
+            Some((
+                body2entity.get(&parent)?,
+                body2entity.get(&link.rigid_body_handle())?,
+            ))
+        }) {
+            commands.entity(*entity2).insert(MultibodyJoint::new(
+                *entity1,
+                GenericJoint {
+                    raw: link.joint.data,
+                },
+            ));
+        }
+    }
+}
+
+/// Inserts the marker components that can’t be part of the `ColliderBundle`, and the
+/// render mesh saved with the collider.
+fn insert_collider_components(
+    entity: &mut EntityCommands,
+    collider: &RapierCollider,
+    saved: Option<&SavedComponents>,
+) {
+    if collider.is_sensor() {
+        entity.insert(Sensor);
+    }
+    if !collider.is_enabled() {
+        entity.insert(ColliderDisabled);
+    }
+    if let Some(render_mesh) = saved.and_then(|saved| saved.render_mesh.clone()) {
+        entity.insert(render_mesh);
+    }
+}
+
+/// Inserts the character controller saved with a body or collider.
+fn insert_character_controller(entity: &mut EntityCommands, saved: Option<&SavedComponents>) {
+    if let Some((controller, options)) = saved.and_then(|saved| saved.character_controller.clone())
+    {
+        entity
+            .insert(controller)
+            .insert(options)
+            .insert(KinematicCharacterControllerOutput::default());
+    }
+}
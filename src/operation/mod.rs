@@ -1,23 +1,39 @@
 pub use self::operations::{Operation, Operations};
-pub use self::plugin::RapierOperationsPlugin;
+pub use self::plugin::{RapierHistoryPlugin, RapierOperationsPlugin};
 
 pub use self::add_collision_shape::add_collision_shape;
-pub use self::add_intersection::{add_intersection, update_intersection, PersistentIntersection};
+pub use self::add_intersection::{
+    add_intersection, update_intersection, BooleanOp, PersistentIntersection,
+};
+pub use self::add_joint::{add_joint, JointKind};
 pub use self::add_plane::add_plane;
 pub use self::clear_scene::clear_scene;
+pub use self::cut::cut;
+pub use self::export_scene::export_scene;
+pub use self::history::{record_history, SceneHistory, SceneSnapshot, SnapshotComponents};
 
 #[cfg(feature = "dim3")]
-pub use self::import_mesh::{import_mesh, set_trimesh_flags};
+pub use self::import_mesh::{
+    draw_mesh_import_preview, import_mesh, set_trimesh_flags, MeshImportPreview,
+};
 pub use self::import_scene::import_scene;
+pub use self::paste::{paste, Clipboard, CopiedObjects};
+pub use self::timeline::{record_timeline, restore_timeline, rewind_timeline, SimulationTimeline};
 
 mod operations;
 mod plugin;
 
 mod add_collision_shape;
 mod add_intersection;
+mod add_joint;
 mod add_plane;
 mod clear_scene;
+mod cut;
+mod export_scene;
+mod history;
 
 #[cfg(feature = "dim3")]
 mod import_mesh;
 mod import_scene;
+mod paste;
+mod timeline;
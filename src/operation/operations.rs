@@ -1,20 +1,69 @@
 use bevy::prelude::*;
 
+use crate::operation::{BooleanOp, CopiedObjects, JointKind, SceneSnapshot};
 use crate::utils::{ColliderBundle, RigidBodyBundle};
+use bevy_rapier::geometry::Collider;
 #[cfg(feature = "dim3")]
 use bevy_rapier::geometry::ComputedColliderShape;
+use bevy_rapier::math::Vect;
 use bevy_rapier::plugin::RapierContext;
 use std::path::PathBuf;
 
 pub enum Operation {
     #[cfg(feature = "dim3")]
     ImportMesh(PathBuf, ComputedColliderShape),
+    #[cfg(feature = "dim3")]
+    PreviewMesh(PathBuf, ComputedColliderShape),
+    #[cfg(feature = "dim3")]
+    ClearMeshPreview,
     AddPlane, // { start: Point<f32>, stop: Point<f32> },
-    AddCollider(ColliderBundle, RigidBodyBundle, Transform),
-    AddIntersection,
+    /// Spawns a collider, on a new rigid-body if one is given.
+    AddCollider(ColliderBundle, Option<RigidBodyBundle>, Transform),
+    AddIntersection {
+        op: BooleanOp,
+        bake: bool,
+    },
+    /// Cuts the bodies touched by `blade` along the plane passing through `point`.
+    Cut {
+        point: Vect,
+        normal: Vect,
+        blade: Vec<Collider>,
+    },
+    /// Attaches `body2` to `body1`, with world-space anchors.
+    AddJoint {
+        body1: Entity,
+        body2: Entity,
+        anchor1: Vect,
+        anchor2: Vect,
+        normal: Vect,
+        kind: JointKind,
+        multibody: bool,
+    },
+    /// Spawns copies of the given objects, centered at `position` if there is one.
+    PasteObjects {
+        objects: CopiedObjects,
+        position: Option<Vect>,
+    },
     ExportScene(PathBuf),
     ImportScene(RapierContext),
+    RestoreScene(SceneSnapshot),
     ClearScene,
+    /// Despawns the given entities with their children.
+    Despawn(Vec<Entity>),
+}
+
+impl Operation {
+    /// Does this operation modify the scene?
+    ///
+    /// Only these operations are recorded in the undo history.
+    pub fn is_scene_edit(&self) -> bool {
+        match self {
+            #[cfg(feature = "dim3")]
+            Self::PreviewMesh(..) | Self::ClearMeshPreview => false,
+            Self::ExportScene(_) | Self::RestoreScene(_) => false,
+            _ => true,
+        }
+    }
 }
 
 #[derive(Resource)]
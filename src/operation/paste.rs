@@ -0,0 +1,343 @@
+use crate::control::CharacterControlOptions;
+use crate::operation::{Operation, Operations};
+use crate::render::ColliderRender;
+use crate::selection::Selection;
+use crate::styling::ColorGenerator;
+use crate::utils::{ColliderBundle, ColliderRenderBundle, RigidBodyBundle};
+use bevy::prelude::*;
+use bevy_rapier::prelude::*;
+use bevy_rapier::rapier::geometry::Collider as RapierCollider;
+use bevy_rapier::rapier::math::Isometry;
+use bevy_rapier::utils::iso_to_transform;
+
+/// A collider of a copied object.
+#[derive(Clone)]
+struct CopiedCollider {
+    /// Is the collider attached to the same entity as its rigid-body?
+    on_body_entity: bool,
+    /// The position relative to the rigid-body, or the world-space position if there is none.
+    transform: Transform,
+    collider: ColliderBundle,
+    sensor: bool,
+    disabled: bool,
+    color: Option<Color>,
+    name: Option<Name>,
+}
+
+impl CopiedCollider {
+    fn new(
+        collider: &RapierCollider,
+        entity: Entity,
+        transform: Transform,
+        on_body_entity: bool,
+        renders: &Query<(Entity, &ColliderRender)>,
+        names: &Query<&Name>,
+    ) -> Self {
+        Self {
+            on_body_entity,
+            transform,
+            collider: ColliderBundle::from(collider),
+            sensor: collider.is_sensor(),
+            disabled: !collider.is_enabled(),
+            color: renders.get(entity).ok().map(|(_, render)| render.color),
+            name: names.get(entity).ok().cloned(),
+        }
+    }
+
+    fn insert(&self, entity: &mut EntityCommands, colors: &mut ColorGenerator) {
+        entity.insert(self.collider.clone()).insert(
+            self.color
+                .map(ColliderRenderBundle::with_color)
+                .unwrap_or_else(|| ColliderRenderBundle::new(colors)),
+        );
+        if self.sensor {
+            entity.insert(Sensor);
+        }
+        if self.disabled {
+            entity.insert(ColliderDisabled);
+        }
+    }
+}
+
+/// A copied rigid-body with its colliders, or a copied collider without rigid-body.
+#[derive(Clone)]
+struct CopiedObject {
+    body: Option<RigidBodyBundle>,
+    disabled: bool,
+    transform: Transform,
+    name: Option<Name>,
+    colliders: Vec<CopiedCollider>,
+    character_controller: Option<(KinematicCharacterController, CharacterControlOptions)>,
+}
+
+/// A copied joint, between two copied objects identified by their index.
+#[derive(Clone)]
+struct CopiedJoint {
+    object1: usize,
+    object2: usize,
+    joint: GenericJoint,
+    multibody: bool,
+}
+
+/// A copy of some objects of the scene, with the joints between them.
+#[derive(Clone, Default)]
+pub struct CopiedObjects {
+    objects: Vec<CopiedObject>,
+    joints: Vec<CopiedJoint>,
+}
+
+impl CopiedObjects {
+    /// Copies the given entities, or the rigid-bodies they are attached to.
+    ///
+    /// Only the joints between two copied rigid-bodies are copied.
+    pub fn new(
+        context: &RapierContext,
+        entities: impl Iterator<Item = Entity>,
+        renders: &Query<(Entity, &ColliderRender)>,
+        names: &Query<&Name>,
+        character_controllers: &Query<(&KinematicCharacterController, &CharacterControlOptions)>,
+    ) -> Self {
+        let mut roots: Vec<_> = entities
+            .map(|entity| context.collider_parent(entity).unwrap_or(entity))
+            .collect();
+        roots.sort();
+        roots.dedup();
+
+        let mut result = Self::default();
+        let mut copied_bodies = vec![];
+
+        for entity in roots {
+            let character_controller = character_controllers
+                .get(entity)
+                .ok()
+                .map(|(controller, options)| (controller.clone(), *options));
+
+            if let Some((handle, rb)) = context
+                .entity2body()
+                .get(&entity)
+                .and_then(|h| Some((*h, context.bodies.get(*h)?)))
+            {
+                let colliders = rb
+                    .colliders()
+                    .iter()
+                    .filter_map(|co_handle| {
+                        let co = context.colliders.get(*co_handle)?;
+                        let co_entity = context.collider_entity(*co_handle)?;
+                        let local_pos = co
+                            .position_wrt_parent()
+                            .copied()
+                            .unwrap_or_else(Isometry::identity);
+                        Some(CopiedCollider::new(
+                            co,
+                            co_entity,
+                            iso_to_transform(&local_pos),
+                            co_entity == entity,
+                            renders,
+                            names,
+                        ))
+                    })
+                    .collect();
+
+                copied_bodies.push((handle, result.objects.len()));
+                result.objects.push(CopiedObject {
+                    body: Some(RigidBodyBundle::from(rb)),
+                    disabled: !rb.is_enabled(),
+                    transform: iso_to_transform(rb.position()),
+                    name: names.get(entity).ok().cloned(),
+                    colliders,
+                    character_controller,
+                });
+            } else if let Some(co) = context
+                .entity2collider()
+                .get(&entity)
+                .and_then(|h| context.colliders.get(*h))
+            {
+                result.objects.push(CopiedObject {
+                    body: None,
+                    disabled: false,
+                    transform: iso_to_transform(co.position()),
+                    name: names.get(entity).ok().cloned(),
+                    colliders: vec![CopiedCollider::new(
+                        co,
+                        entity,
+                        Transform::IDENTITY,
+                        true,
+                        renders,
+                        names,
+                    )],
+                    character_controller,
+                });
+            }
+        }
+
+        let object_id = |handle| {
+            copied_bodies
+                .iter()
+                .find(|(h, _)| *h == handle)
+                .map(|(_, id)| *id)
+        };
+
+        for (_, joint) in context.impulse_joints.iter() {
+            if let (Some(object1), Some(object2)) = (object_id(joint.body1), object_id(joint.body2))
+            {
+                result.joints.push(CopiedJoint {
+                    object1,
+                    object2,
+                    joint: GenericJoint { raw: joint.data },
+                    multibody: false,
+                });
+            }
+        }
+
+        for (_, _, multibody, link) in context.multibody_joints.iter() {
+            let parent = link
+                .parent_id()
+                .and_then(|id| multibody.link(id))
+                .map(|parent| parent.rigid_body_handle());
+
+            if let (Some(object1), Some(object2)) = (
+                parent.and_then(object_id),
+                object_id(link.rigid_body_handle()),
+            ) {
+                result.joints.push(CopiedJoint {
+                    object1,
+                    object2,
+                    joint: GenericJoint {
+                        raw: link.joint.data,
+                    },
+                    multibody: true,
+                });
+            }
+        }
+
+        result
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// The average position of the copied objects.
+    fn center(&self) -> Vect {
+        let sum: Vec3 = self
+            .objects
+            .iter()
+            .map(|obj| obj.transform.translation)
+            .sum();
+        let center = sum / self.objects.len() as f32;
+        #[cfg(feature = "dim2")]
+        {
+            center.truncate()
+        }
+        #[cfg(feature = "dim3")]
+        {
+            center
+        }
+    }
+}
+
+/// The objects copied with Ctrl+C, pasted with Ctrl+V.
+#[derive(Resource, Default)]
+pub struct Clipboard {
+    pub objects: Option<CopiedObjects>,
+}
+
+pub fn paste(
+    mut commands: Commands,
+    operations: Res<Operations>,
+    mut colors: ResMut<ColorGenerator>,
+    mut selections: Query<&mut Selection>,
+) {
+    for op in operations.iter() {
+        if let Operation::PasteObjects { objects, position } = op {
+            if objects.is_empty() {
+                continue;
+            }
+
+            // The copies replace the current selection.
+            for mut selection in selections.iter_mut() {
+                selection.selected = false;
+            }
+
+            #[cfg(feature = "dim2")]
+            let shift = position
+                .map(|position| (position - objects.center()).extend(0.0))
+                .unwrap_or(Vec3::ZERO);
+            // In 3D, the objects are moved horizontally, keeping their height.
+            #[cfg(feature = "dim3")]
+            let shift = position
+                .map(|position| (position - objects.center()) * Vec3::new(1.0, 0.0, 1.0))
+                .unwrap_or(Vec3::ZERO);
+
+            let entities: Vec<_> = objects
+                .objects
+                .iter()
+                .map(|object| spawn_object(&mut commands, object, shift, &mut colors))
+                .collect();
+
+            for joint in &objects.joints {
+                let (entity1, entity2) = (entities[joint.object1], entities[joint.object2]);
+                if joint.multibody {
+                    commands
+                        .entity(entity2)
+                        .insert(MultibodyJoint::new(entity1, joint.joint));
+                } else {
+                    commands.entity(entity2).with_children(|cmd| {
+                        cmd.spawn(ImpulseJoint::new(entity1, joint.joint))
+                            .insert(Name::new("Impulse joint"));
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn spawn_object(
+    commands: &mut Commands,
+    object: &CopiedObject,
+    shift: Vec3,
+    colors: &mut ColorGenerator,
+) -> Entity {
+    let mut transform = object.transform;
+    transform.translation += shift;
+
+    let mut entity = commands.spawn(TransformBundle::from_transform(transform));
+    entity
+        .insert(VisibilityBundle::default())
+        .insert(Selection { selected: true });
+
+    if let Some(body) = &object.body {
+        entity.insert(*body);
+    }
+    if object.disabled {
+        entity.insert(RigidBodyDisabled);
+    }
+    if let Some(name) = &object.name {
+        entity.insert(name.clone());
+    }
+    if let Some((controller, options)) = &object.character_controller {
+        entity
+            .insert(controller.clone())
+            .insert(*options)
+            .insert(KinematicCharacterControllerOutput::default());
+    }
+
+    for collider in &object.colliders {
+        if collider.on_body_entity {
+            collider.insert(&mut entity, colors);
+        } else {
+            entity.with_children(|cmd| {
+                let mut child = cmd.spawn(TransformBundle::from_transform(collider.transform));
+                child.insert(
+                    collider
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| Name::new("Collision Shape")),
+                );
+                collider.insert(&mut child, colors);
+            });
+        }
+    }
+
+    entity.id()
+}
@@ -1,12 +1,14 @@
-use crate::operation::{self, Operations};
+use crate::operation::{self, Operations, SceneHistory, SimulationTimeline};
 use crate::render::RenderSystems;
 use bevy::prelude::*;
+use bevy_rapier::plugin::PhysicsSet;
 
 pub struct RapierOperationsPlugin;
 
 impl Plugin for RapierOperationsPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(Operations::default())
+            .insert_resource(operation::Clipboard::default())
             .add_systems(Last, clear_operations)
             .add_systems(
                 Update,
@@ -24,6 +26,18 @@ impl Plugin for RapierOperationsPlugin {
                 Update,
                 operation::update_intersection.in_set(RenderSystems::ProcessCommands),
             )
+            .add_systems(
+                Update,
+                operation::cut.in_set(RenderSystems::ProcessCommands),
+            )
+            .add_systems(
+                Update,
+                operation::add_joint.in_set(RenderSystems::ProcessCommands),
+            )
+            .add_systems(
+                Update,
+                operation::paste.in_set(RenderSystems::ProcessCommands),
+            )
             .add_systems(
                 Update,
                 operation::import_scene
@@ -33,15 +47,58 @@ impl Plugin for RapierOperationsPlugin {
             .add_systems(
                 Update,
                 operation::clear_scene.in_set(RenderSystems::ProcessCommands),
+            )
+            .add_systems(
+                Update,
+                operation::export_scene.in_set(RenderSystems::ProcessCommands),
             );
         #[cfg(feature = "dim3")]
         {
-            app.add_systems(Update, operation::set_trimesh_flags)
-                .add_systems(Update, operation::import_mesh);
+            app.insert_resource(operation::MeshImportPreview::default())
+                .add_systems(Update, operation::set_trimesh_flags)
+                .add_systems(
+                    Update,
+                    operation::import_mesh.in_set(RenderSystems::ProcessCommands),
+                )
+                .add_systems(Update, operation::draw_mesh_import_preview);
         }
     }
 }
 
+/// Records the undo history and the simulation timeline of the scene edited in the UI.
+pub struct RapierHistoryPlugin;
+
+impl Plugin for RapierHistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SceneHistory::default())
+            .insert_resource(SimulationTimeline::default())
+            .add_systems(
+                Update,
+                operation::record_history
+                    .after(RenderSystems::BeforeCommands)
+                    .before(RenderSystems::ProcessCommands),
+            )
+            .add_systems(
+                Update,
+                operation::rewind_timeline
+                    .after(operation::record_history)
+                    .before(RenderSystems::ProcessCommands),
+            )
+            .add_systems(
+                PostUpdate,
+                operation::restore_timeline
+                    .after(PhysicsSet::SyncBackend)
+                    .before(PhysicsSet::StepSimulation),
+            )
+            .add_systems(
+                PostUpdate,
+                operation::record_timeline
+                    .after(operation::restore_timeline)
+                    .before(PhysicsSet::StepSimulation),
+            );
+    }
+}
+
 fn clear_operations(mut operations: ResMut<Operations>) {
     operations.clear();
 }
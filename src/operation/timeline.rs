@@ -0,0 +1,320 @@
+use crate::operation::{Operation, Operations, SceneSnapshot, SnapshotComponents};
+use crate::utils::{ColliderBundle, RigidBodyBundle};
+use crate::PhysicsProgress;
+use bevy::prelude::*;
+use bevy_rapier::math::Real;
+use bevy_rapier::prelude::*;
+use bevy_rapier::rapier::math::Isometry;
+
+pub const DEFAULT_SNAPSHOT_INTERVAL: usize = 30;
+pub const DEFAULT_TIMELINE_LEN: usize = 200;
+
+/// Periodic snapshots of the simulation, taken while it is running.
+///
+/// Rewinding to one of the snapshots restores the scene as it was at that time. The
+/// snapshots taken after that time are discarded once the simulation resumes.
+///
+/// When the scene still has the bodies, colliders, and joints of the snapshot, the whole
+/// physics context is restored in place, including its contacts, islands, and joint
+/// states, so resuming the simulation replays the steps that were originally simulated.
+/// If they were added or removed since then, the scene is respawned from the snapshot.
+#[derive(Resource)]
+pub struct SimulationTimeline {
+    snapshots: Vec<SceneSnapshot>,
+    /// Number of simulation steps between two consecutive snapshots.
+    pub interval: usize,
+    max_len: usize,
+    rewind_requested: Option<usize>,
+    pending_restore: Option<PendingRestore>,
+}
+
+/// A snapshot being rewound to, waiting for `restore_timeline` to replace the physics context.
+struct PendingRestore {
+    scene: RapierContext,
+    simulated_time: Real,
+    simulated_steps: usize,
+}
+
+impl Default for SimulationTimeline {
+    fn default() -> Self {
+        Self::new(DEFAULT_SNAPSHOT_INTERVAL, DEFAULT_TIMELINE_LEN)
+    }
+}
+
+impl SimulationTimeline {
+    pub fn new(interval: usize, max_len: usize) -> Self {
+        Self {
+            snapshots: vec![],
+            interval,
+            max_len,
+            rewind_requested: None,
+            pending_restore: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// The index of the last snapshot taken before the given simulation step.
+    pub fn index_at(&self, simulated_steps: usize) -> Option<usize> {
+        self.snapshots
+            .iter()
+            .rposition(|snapshot| snapshot.simulated_steps <= simulated_steps)
+    }
+
+    /// Requests the scene to be restored to the `i`-th snapshot.
+    pub fn rewind(&mut self, i: usize) {
+        if i < self.snapshots.len() {
+            self.rewind_requested = Some(i);
+        }
+    }
+
+    fn push(&mut self, snapshot: SceneSnapshot) {
+        // Anything after this snapshot belongs to a timeline that has been rewound.
+        self.snapshots
+            .retain(|s| s.simulated_steps < snapshot.simulated_steps);
+        self.snapshots.push(snapshot);
+
+        if self.snapshots.len() > self.max_len {
+            let excess = self.snapshots.len() - self.max_len;
+            self.snapshots.drain(..excess);
+        }
+    }
+}
+
+/// Takes a snapshot of the simulation every `SimulationTimeline::interval` steps.
+///
+/// This runs right before the physics step, once the ECS changes have been synchronized
+/// with the physics context, so the snapshot matches the state being simulated.
+pub fn record_timeline(
+    mut timeline: ResMut<SimulationTimeline>,
+    config: Res<RapierConfiguration>,
+    context: Res<RapierContext>,
+    progress: Res<PhysicsProgress>,
+    components: SnapshotComponents,
+) {
+    if !config.physics_pipeline_active
+        || timeline.interval == 0
+        || progress.simulated_steps % timeline.interval != 0
+    {
+        return;
+    }
+
+    match SceneSnapshot::new(&context, &progress, &components) {
+        Ok(snapshot) => timeline.push(snapshot),
+        Err(e) => error!("Failed to record the simulation timeline: {:?}", e),
+    }
+}
+
+/// Restores the scene to the snapshot selected on the timeline.
+///
+/// If the bodies, colliders, and joints of the scene are still the ones of the snapshot,
+/// their components are reset here and the physics context is replaced in place by
+/// `restore_timeline`. Otherwise, the scene is respawned from the snapshot.
+#[allow(clippy::too_many_arguments)]
+pub fn rewind_timeline(
+    mut commands: Commands,
+    mut timeline: ResMut<SimulationTimeline>,
+    mut operations: ResMut<Operations>,
+    context: Res<RapierContext>,
+    mut transforms: Query<&mut Transform>,
+    parents: Query<&Parent>,
+    mut impulse_joints: Query<&mut ImpulseJoint>,
+    mut multibody_joints: Query<&mut MultibodyJoint>,
+) {
+    if operations
+        .iter()
+        .any(|op| matches!(op, Operation::ClearScene))
+    {
+        // The scene is being replaced, the recorded timeline doesn’t apply anymore.
+        timeline.snapshots.clear();
+        timeline.rewind_requested = None;
+        timeline.pending_restore = None;
+        return;
+    }
+
+    let Some(i) = timeline.rewind_requested.take() else {
+        return;
+    };
+    // The snapshot stays on the timeline so the user can keep scrubbing back and forth.
+    let Some(snapshot) = timeline.snapshots.get(i).cloned() else {
+        return;
+    };
+    let scene = match snapshot.context() {
+        Ok(scene) => scene,
+        Err(e) => {
+            error!("Failed to rewind the simulation timeline: {:?}", e);
+            return;
+        }
+    };
+
+    if !has_same_structure(&context, &scene) {
+        operations.push(Operation::ClearScene);
+        operations.push(Operation::RestoreScene(snapshot));
+        return;
+    }
+
+    for (_, body) in scene.bodies.iter() {
+        let entity = Entity::from_bits(body.user_data as u64);
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert(RigidBodyBundle::from(body));
+        if body.is_enabled() {
+            entity_commands.remove::<RigidBodyDisabled>();
+        } else {
+            entity_commands.insert(RigidBodyDisabled);
+        }
+
+        // The position of the body is in world-space.
+        if !parents.contains(entity) {
+            if let Ok(mut transform) = transforms.get_mut(entity) {
+                set_isometry(&mut transform, body.position());
+            }
+        }
+    }
+
+    for (_, collider) in scene.colliders.iter() {
+        let entity = Entity::from_bits(collider.user_data as u64);
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert(ColliderBundle::from(collider));
+        if collider.is_sensor() {
+            entity_commands.insert(Sensor);
+        } else {
+            entity_commands.remove::<Sensor>();
+        }
+        if collider.is_enabled() {
+            entity_commands.remove::<ColliderDisabled>();
+        } else {
+            entity_commands.insert(ColliderDisabled);
+        }
+
+        // Colliders on the body entity already moved with it.
+        let on_body_entity = collider
+            .parent()
+            .and_then(|parent| scene.bodies.get(parent))
+            .is_some_and(|body| body.user_data == collider.user_data);
+        if !on_body_entity {
+            let position = collider
+                .position_wrt_parent()
+                .unwrap_or_else(|| collider.position());
+            if let Ok(mut transform) = transforms.get_mut(entity) {
+                set_isometry(&mut transform, position);
+            }
+        }
+    }
+
+    for (entity, handle) in context.entity2impulse_joint() {
+        if let (Ok(mut joint), Some(saved)) = (
+            impulse_joints.get_mut(*entity),
+            scene.impulse_joints.get(*handle),
+        ) {
+            let data: &mut GenericJoint = joint.data.as_mut();
+            data.raw = saved.data;
+        }
+    }
+
+    for (entity, handle) in context.entity2multibody_joint() {
+        let saved = scene
+            .multibody_joints
+            .get(*handle)
+            .and_then(|(multibody, link_id)| multibody.link(link_id));
+        if let (Ok(mut joint), Some(saved)) = (multibody_joints.get_mut(*entity), saved) {
+            let data: &mut GenericJoint = joint.data.as_mut();
+            data.raw = saved.joint.data;
+        }
+    }
+
+    timeline.pending_restore = Some(PendingRestore {
+        scene,
+        simulated_time: snapshot.simulated_time,
+        simulated_steps: snapshot.simulated_steps,
+    });
+}
+
+/// Replaces the physics context by the one of the snapshot being rewound to.
+///
+/// This runs once the component changes made by `rewind_timeline` have been synchronized
+/// with the physics context, so the contacts, islands, and solver caches of the snapshot
+/// aren’t altered before the next step.
+pub fn restore_timeline(
+    mut timeline: ResMut<SimulationTimeline>,
+    mut context: ResMut<RapierContext>,
+    mut progress: ResMut<PhysicsProgress>,
+) {
+    let Some(restore) = timeline.pending_restore.take() else {
+        return;
+    };
+    let scene = restore.scene;
+
+    // The entity mappings and the physics pipeline aren’t part of the snapshot, and the
+    // handles didn’t change, so the ones of the current context are kept.
+    context.islands = scene.islands;
+    context.broad_phase = scene.broad_phase;
+    context.narrow_phase = scene.narrow_phase;
+    context.bodies = scene.bodies;
+    context.colliders = scene.colliders;
+    context.impulse_joints = scene.impulse_joints;
+    context.multibody_joints = scene.multibody_joints;
+    context.ccd_solver = scene.ccd_solver;
+    context.query_pipeline = scene.query_pipeline;
+    context.integration_parameters = scene.integration_parameters;
+
+    progress.simulated_time = restore.simulated_time;
+    progress.simulated_steps = restore.simulated_steps;
+}
+
+/// Are the bodies, colliders, and joints of `scene` the ones of `context`, with the
+/// same handles and entities?
+fn has_same_structure(context: &RapierContext, scene: &RapierContext) -> bool {
+    let same_bodies = context.bodies.len() == scene.bodies.len()
+        && scene.bodies.iter().all(|(handle, body)| {
+            context
+                .bodies
+                .get(handle)
+                .is_some_and(|current| current.user_data == body.user_data)
+        });
+    let same_colliders = context.colliders.len() == scene.colliders.len()
+        && scene.colliders.iter().all(|(handle, collider)| {
+            context.colliders.get(handle).is_some_and(|current| {
+                current.user_data == collider.user_data && current.parent() == collider.parent()
+            })
+        });
+    let same_impulse_joints = context.entity2impulse_joint().len() == scene.impulse_joints.len()
+        && context.impulse_joints.len() == scene.impulse_joints.len()
+        && scene.impulse_joints.iter().all(|(handle, joint)| {
+            context
+                .impulse_joints
+                .get(handle)
+                .is_some_and(|current| current.body1 == joint.body1 && current.body2 == joint.body2)
+        });
+    let same_multibody_joints = context.entity2multibody_joint().len()
+        == scene.multibody_joints.iter().count()
+        && context
+            .entity2multibody_joint()
+            .values()
+            .all(|handle| scene.multibody_joints.get(*handle).is_some());
+
+    same_bodies && same_colliders && same_impulse_joints && same_multibody_joints
+}
+
+/// Sets the translation and rotation of `transform`, keeping its scale.
+fn set_isometry(transform: &mut Transform, position: &Isometry<Real>) {
+    let restored = bevy_rapier::utils::iso_to_transform(position);
+    #[cfg(feature = "dim2")]
+    {
+        // Keep the depth used to order the 2D renders.
+        transform.translation = restored
+            .translation
+            .truncate()
+            .extend(transform.translation.z);
+    }
+    #[cfg(feature = "dim3")]
+    {
+        transform.translation = restored.translation;
+    }
+    transform.rotation = restored.rotation;
+}
@@ -37,11 +37,11 @@ pub fn handle_projectile_click(
                         mass_properties: ColliderMassProperties::Density(1000.0),
                         ..ColliderBundle::new(Collider::ball(0.3))
                     },
-                    RigidBodyBundle {
+                    Some(RigidBodyBundle {
                         velocity: Velocity::linear(ray_dir * 400.0),
                         ccd: Ccd::enabled(),
                         ..RigidBodyBundle::dynamic()
-                    },
+                    }),
                     Transform::from_translation(ray_pos),
                 ));
             }
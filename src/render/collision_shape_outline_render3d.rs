@@ -1,13 +1,81 @@
 use crate::cli::CliArgs;
-use crate::render::{ColliderOutlineRender, ColliderRenderTargets};
+use crate::render::collision_shape_render::{collision_shape_trimesh, color_key, ShapeKey};
+use crate::render::{
+    ColliderOutlineRender, ColliderRender, ColliderRenderTargets, ColliderStateColor, RenderMode,
+    RenderedCollider, HIGHLIGHT_OUTLINE_WIDTH, HOVER_OUTLINE_COLOR, SELECTION_OUTLINE_COLOR,
+};
+use crate::selection::{SceneMouse, SelectableSceneObject, Selection};
 use bevy::prelude::*;
+use bevy_egui::egui::ahash::HashMap;
 use bevy_polyline::prelude::*;
 use bevy_rapier::prelude::{Collider, ColliderView};
-use bevy_rapier::rapier::math::{Point, Real};
+use bevy_rapier::rapier::math::{Point, Real, Vector};
+
+/// The polylines and materials shared by the collider outlines, kept as weak handles like
+/// the collider meshes.
+#[derive(Resource, Default, Clone)]
+pub struct CollisionShapeOutlineInstances {
+    shape_to_polyline: HashMap<ShapeKey, Handle<Polyline>>,
+    materials: HashMap<([u32; 4], u32, bool), Handle<PolylineMaterial>>,
+}
+
+impl CollisionShapeOutlineInstances {
+    fn polyline(
+        &mut self,
+        collider: &Collider,
+        polylines: &mut Assets<Polyline>,
+    ) -> Option<Handle<Polyline>> {
+        let shape = collider.as_unscaled_typed_shape();
+        let key = ShapeKey::new(&shape);
+
+        if let Some(polyline) = key
+            .as_ref()
+            .and_then(|key| self.shape_to_polyline.get(key))
+            .and_then(|polyline| polylines.get_strong_handle(polyline.id()))
+        {
+            return Some(polyline);
+        }
+
+        let (vertices, indices) = collision_shape_outline(shape)?;
+        let polyline = polylines.add(gen_bevy_polyline(&vertices, &indices)?);
+        if let Some(key) = key {
+            self.shape_to_polyline.insert(key, polyline.clone_weak());
+        }
+        Some(polyline)
+    }
+
+    /// The material of an outline. Highlighted outlines are drawn on top of everything else.
+    fn material(
+        &mut self,
+        color: Color,
+        width: f32,
+        highlighted: bool,
+        materials: &mut Assets<PolylineMaterial>,
+    ) -> Handle<PolylineMaterial> {
+        let key = (color_key(color), width.to_bits(), highlighted);
+        if let Some(material) = self
+            .materials
+            .get(&key)
+            .and_then(|material| materials.get_strong_handle(material.id()))
+        {
+            return material;
+        }
+
+        let material = materials.add(PolylineMaterial {
+            color: color.into(),
+            width,
+            depth_bias: if highlighted { -1.0 } else { 0.0 },
+            perspective: false,
+        });
+        self.materials.insert(key, material.clone_weak());
+        material
+    }
+}
 
 pub fn create_collider_outline_renders_system(
     mut commands: Commands,
     cli: Res<CliArgs>,
+    mut instances: ResMut<CollisionShapeOutlineInstances>,
     mut polylines: ResMut<Assets<Polyline>>,
     mut materials: ResMut<Assets<PolylineMaterial>>,
     mut coll_shape_render: Query<
@@ -17,38 +85,28 @@ pub fn create_collider_outline_renders_system(
             &ColliderOutlineRender,
             &mut ColliderRenderTargets,
         ),
-        Or<(Changed<Collider>, Changed<ColliderOutlineRender>)>,
+        Or<(Changed<RenderedCollider>, Changed<ColliderOutlineRender>)>,
     >,
     existing_entities: Query<Entity>,
-    old_transform: Query<&Transform>,
 ) {
     if cli.lower_graphics {
         return;
     }
 
     for (entity, collider, render, mut render_target) in coll_shape_render.iter_mut() {
-        if let Some(polyline) = generate_collision_shape_render_outline(collider) {
-            let material = PolylineMaterial {
-                color: render.color,
-                width: render.thickness,
-                perspective: true,
-                ..Default::default()
-            };
-
-            let mut bundle = PolylineBundle {
-                polyline: polylines.add(polyline),
-                material: materials.add(material),
+        if let Some(polyline) = instances.polyline(collider, &mut polylines) {
+            let bundle = PolylineBundle {
+                polyline,
+                material: instances.material(render.color, render.thickness, false, &mut materials),
                 ..Default::default()
             };
 
             if let Some(target) = render_target.outline_target {
                 if existing_entities.get(target).is_ok() {
-                    let old_transform = old_transform.get(target).unwrap();
-                    bundle.transform = *old_transform;
                     commands.entity(target).insert(bundle);
                 }
             } else {
-                let target = commands.entity(entity).with_children(|cmd| {
+                commands.entity(entity).with_children(|cmd| {
                     let target = cmd
                         .spawn(bundle)
                         .insert(Name::new("Collider Outlines"))
@@ -60,35 +118,180 @@ pub fn create_collider_outline_renders_system(
     }
 }
 
-fn generate_collision_shape_render_outline(collider: &Collider) -> Option<Polyline> {
+/// Shows the surfaces and outlines of the colliders depending on the render mode, and
+/// highlights the outlines of the selected and hovered colliders.
+#[allow(clippy::too_many_arguments)]
+pub fn update_collider_outlines(
+    render_mode: Res<RenderMode>,
+    scene_mouse: Res<SceneMouse>,
+    mut instances: ResMut<CollisionShapeOutlineInstances>,
+    mut materials: ResMut<Assets<PolylineMaterial>>,
+    colliders: Query<(
+        Entity,
+        &ColliderRender,
+        Option<&ColliderStateColor>,
+        &ColliderOutlineRender,
+        &ColliderRenderTargets,
+        Option<&Parent>,
+    )>,
+    selections: Query<&Selection>,
+    mut visibilities: Query<&mut Visibility>,
+    mut outline_materials: Query<&mut Handle<PolylineMaterial>>,
+) {
+    let hovered = match scene_mouse.hovered {
+        Some(SelectableSceneObject::Collider(entity, _)) => Some(entity),
+        _ => None,
+    };
+    let is_selected = |entity| selections.get(entity).is_ok_and(|s| s.selected());
+
+    for (entity, render, state_color, outline, targets, parent) in colliders.iter() {
+        if let Some(mut visibility) = targets.target.and_then(|e| visibilities.get_mut(e).ok()) {
+            let wanted = if render_mode.shows_surfaces() {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+            if *visibility != wanted {
+                *visibility = wanted;
+            }
+        }
+
+        let Some(outline_target) = targets.outline_target else {
+            continue;
+        };
+
+        // The colliders of a selected rigid-body are highlighted too.
+        let selected = is_selected(entity) || parent.is_some_and(|p| is_selected(p.get()));
+        let (color, width, highlighted) = if hovered == Some(entity) {
+            (HOVER_OUTLINE_COLOR, HIGHLIGHT_OUTLINE_WIDTH, true)
+        } else if selected {
+            (SELECTION_OUTLINE_COLOR, HIGHLIGHT_OUTLINE_WIDTH, true)
+        } else if *render_mode == RenderMode::Wireframe {
+            // Without surfaces, the outlines have the color of the collider.
+            let color = state_color.and_then(|c| c.0).unwrap_or(render.color);
+            (color, outline.thickness, false)
+        } else {
+            (outline.color, outline.thickness, false)
+        };
+
+        if let Ok(mut visibility) = visibilities.get_mut(outline_target) {
+            let wanted = if highlighted || render_mode.shows_outlines() {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+            if *visibility != wanted {
+                *visibility = wanted;
+            }
+        }
+
+        if let Ok(mut material) = outline_materials.get_mut(outline_target) {
+            let wanted = instances.material(color, width, highlighted, &mut materials);
+            if *material != wanted {
+                *material = wanted;
+            }
+        }
+    }
+}
+
+/// The vertices and segments of the edges of a shape.
+fn collision_shape_outline(shape: ColliderView) -> Option<(Vec<Point<Real>>, Vec<[u32; 2]>)> {
     const NSUB: u32 = 20;
+    const BORDER_NSUB: u32 = 5;
 
-    let (vertices, indices) = match collider.as_unscaled_typed_shape() {
+    let result = match shape {
         ColliderView::Cuboid(s) => s.raw.to_outline(),
         ColliderView::Ball(s) => s.raw.to_outline(NSUB),
         ColliderView::Cylinder(s) => s.raw.to_outline(NSUB),
         ColliderView::Cone(s) => s.raw.to_outline(NSUB),
         ColliderView::Capsule(s) => s.raw.to_outline(NSUB),
-        #[cfg(feature = "voxels")]
-        ColliderView::Voxels(s) => s.raw.to_outline(),
-        ColliderView::ConvexPolyhedron(_s) => todo!(),
-        // ColliderView::Compound(s) => s.raw.to_trimesh(),
-        ColliderView::HeightField(_s) => return None,
-        // ColliderView::Polyline(s) => s.raw.to_trimesh(),
-        // ColliderView::Triangle(s) => s.raw.to_trimesh(),
-        ColliderView::HalfSpace(_s) => {
-            todo!()
+        ColliderView::RoundCuboid(s) => s.raw.to_outline(BORDER_NSUB),
+        ColliderView::RoundTriangle(s) => s.raw.to_outline(BORDER_NSUB),
+        ColliderView::RoundCylinder(s) => s.raw.to_outline(NSUB, BORDER_NSUB),
+        ColliderView::RoundCone(s) => s.raw.to_outline(NSUB, BORDER_NSUB),
+        ColliderView::RoundConvexPolyhedron(s) => s.raw.to_outline(BORDER_NSUB),
+        ColliderView::HeightField(s) => s.raw.to_outline(),
+        ColliderView::Polyline(s) => (s.raw.vertices().to_vec(), s.raw.indices().to_vec()),
+        ColliderView::Segment(s) => (vec![s.raw.a, s.raw.b], vec![[0, 1]]),
+        ColliderView::Triangle(s) => (
+            vec![s.raw.a, s.raw.b, s.raw.c],
+            vec![[0, 1], [1, 2], [2, 0]],
+        ),
+        ColliderView::Compound(s) => {
+            let mut vertices = vec![];
+            let mut indices = vec![];
+
+            for (pos, part) in s.raw.shapes() {
+                let Some((part_vtx, part_idx)) =
+                    collision_shape_outline(part.as_typed_shape().into())
+                else {
+                    continue;
+                };
+                let base_id = vertices.len() as u32;
+                vertices.extend(part_vtx.iter().map(|pt| pos * pt));
+                indices.extend(
+                    part_idx
+                        .iter()
+                        .map(|idx| [idx[0] + base_id, idx[1] + base_id]),
+                );
+            }
+
+            (vertices, indices)
+        }
+        // The other shapes are outlined by the sharp edges of their render mesh.
+        shape => {
+            let ((vertices, indices), _) = collision_shape_trimesh(shape)?;
+            let edges = sharp_edges(&vertices, &indices);
+            (vertices, edges)
         }
-        ColliderView::TriMesh(_s) => return None,
-        _ => todo!(),
     };
 
-    Some(gen_bevy_polyline(&vertices, &indices))
+    Some(result)
+}
+
+/// The edges of a triangle mesh on its border, or between two faces that aren’t coplanar.
+fn sharp_edges(vertices: &[Point<Real>], indices: &[[u32; 3]]) -> Vec<[u32; 2]> {
+    const MIN_COS_ANGLE: Real = 0.999;
+
+    let normal = |idx: &[u32; 3]| -> Option<Vector<Real>> {
+        let [a, b, c] = idx.map(|i| vertices[i as usize]);
+        (b - a).cross(&(c - a)).try_normalize(1.0e-6)
+    };
+
+    // The normal of the first face found for each edge.
+    let mut first_normals = HashMap::default();
+    let mut edges = vec![];
+
+    for idx in indices {
+        let face_normal = normal(idx);
+
+        for k in 0..3 {
+            let (a, b) = (idx[k], idx[(k + 1) % 3]);
+            let edge = [a.min(b), a.max(b)];
+
+            match first_normals.remove(&edge) {
+                Some(other_normal) => {
+                    if let (Some(n1), Some(n2)) = (face_normal, other_normal) {
+                        if n1.dot(&n2) < MIN_COS_ANGLE {
+                            edges.push(edge);
+                        }
+                    }
+                }
+                None => {
+                    first_normals.insert(edge, face_normal);
+                }
+            }
+        }
+    }
+
+    // The edges with a single face are on the border of the mesh.
+    edges.extend(first_normals.into_keys());
+    edges
 }
 
-fn gen_bevy_polyline(pts: &[Point<Real>], indices: &[[u32; 2]]) -> Polyline {
+fn gen_bevy_polyline(pts: &[Point<Real>], indices: &[[u32; 2]]) -> Option<Polyline> {
     let mut vertices = vec![];
-    let mut last_id = indices[0][0];
+    let mut last_id = indices.first()?[0];
 
     for idx in indices {
         if last_id == idx[0] {
@@ -102,5 +305,5 @@ fn gen_bevy_polyline(pts: &[Point<Real>], indices: &[[u32; 2]]) -> Polyline {
         last_id = idx[1];
     }
 
-    Polyline { vertices }
+    Some(Polyline { vertices })
 }
@@ -1,20 +1,180 @@
-use crate::render::{ColliderRender, ColliderRenderTargets};
+use crate::render::{
+    ColliderRender, ColliderRenderMesh, ColliderRenderTargets, ColliderStateColor, RenderMode,
+};
 use bevy::prelude::*;
 use bevy::render::mesh::{Indices, VertexAttributeValues};
 use bevy_rapier::geometry::{Collider, ColliderView};
+use bevy_rapier::math::Vect;
+use bevy_rapier::rapier::geometry::SharedShape;
 use bevy_rapier::rapier::math::{Point, Real, Vector};
 use na::{point, UnitQuaternion};
+use std::sync::Arc;
 
-use crate::cli::CliArgs;
-use crate::parry::shape::Cuboid;
+use crate::parry::shape::Ball;
+#[cfg(feature = "dim3")]
+use crate::parry::shape::Capsule;
+#[cfg(feature = "dim2")]
+use crate::parry::shape::Segment;
+#[cfg(feature = "dim2")]
+use crate::parry::transformation::convex_hull;
+#[cfg(feature = "dim3")]
+use crate::parry::transformation::try_convex_hull;
 #[cfg(feature = "dim2")]
 use bevy::sprite::MaterialMesh2dBundle;
 use bevy_egui::egui::ahash::HashMap;
 
+#[cfg(feature = "dim2")]
+type ColliderMaterial = ColorMaterial;
+#[cfg(feature = "dim3")]
+type ColliderMaterial = StandardMaterial;
+
+/// The meshes and materials shared by the collider renders.
+///
+/// Only weak handles are kept, so the assets are freed once no render uses them anymore, and
+/// re-created the next time they are needed.
 #[derive(Resource, Default, Clone)]
 pub struct CollisionShapeMeshInstances {
-    cuboid_to_mesh: Vec<(Cuboid, Handle<Mesh>)>, // TODO: make this work for all collider types.
-    color_to_material: Vec<(Color, Handle<StandardMaterial>)>,
+    shape_to_mesh: HashMap<ShapeKey, Handle<Mesh>>,
+    color_to_material: HashMap<[u32; 4], Handle<ColliderMaterial>>,
+}
+
+impl CollisionShapeMeshInstances {
+    /// The mesh of a collider shape, generated only if no identical primitive shape was
+    /// rendered before.
+    fn mesh(&mut self, collider: &Collider, meshes: &mut Assets<Mesh>) -> Option<Handle<Mesh>> {
+        let shape = collider.as_unscaled_typed_shape();
+        let key = ShapeKey::new(&shape);
+
+        if let Some(mesh) = key
+            .as_ref()
+            .and_then(|key| self.shape_to_mesh.get(key))
+            .and_then(|mesh| meshes.get_strong_handle(mesh.id()))
+        {
+            return Some(mesh);
+        }
+
+        let mesh = meshes.add(generate_collision_shape_render_mesh(shape)?);
+        if let Some(key) = key {
+            self.shape_to_mesh.insert(key, mesh.clone_weak());
+        }
+        Some(mesh)
+    }
+
+    fn material(
+        &mut self,
+        color: Color,
+        materials: &mut Assets<ColliderMaterial>,
+    ) -> Handle<ColliderMaterial> {
+        let key = color_key(color);
+        if let Some(material) = self
+            .color_to_material
+            .get(&key)
+            .and_then(|material| materials.get_strong_handle(material.id()))
+        {
+            return material;
+        }
+
+        #[cfg(feature = "dim2")]
+        let material = ColorMaterial::from(color);
+        #[cfg(feature = "dim3")]
+        let material = StandardMaterial {
+            double_sided: true,
+            alpha_mode: if color.alpha() < 1.0 {
+                AlphaMode::Blend
+            } else {
+                AlphaMode::Opaque
+            },
+            ..StandardMaterial::from(color)
+        };
+        let material = materials.add(material);
+        self.color_to_material.insert(key, material.clone_weak());
+        material
+    }
+}
+
+/// The bits of the linear components of a color, used to identify the materials with this color.
+pub(super) fn color_key(color: Color) -> [u32; 4] {
+    let LinearRgba {
+        red,
+        green,
+        blue,
+        alpha,
+    } = color.to_linear();
+    [red, green, blue, alpha].map(f32::to_bits)
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+enum ShapeKind {
+    Cuboid,
+    Ball,
+    Capsule,
+    #[cfg(feature = "dim3")]
+    Cylinder,
+    #[cfg(feature = "dim3")]
+    Cone,
+    Convex,
+}
+
+/// The parameters of a primitive shape, identifying its render mesh.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(super) struct ShapeKey {
+    kind: ShapeKind,
+    params: Vec<u32>,
+}
+
+impl ShapeKey {
+    /// The key of a primitive shape, or `None` if meshes of this shape aren’t shared.
+    pub(super) fn new(shape: &ColliderView) -> Option<Self> {
+        let (kind, params): (_, Vec<Real>) = match shape {
+            ColliderView::Cuboid(s) => (
+                ShapeKind::Cuboid,
+                s.raw.half_extents.iter().copied().collect(),
+            ),
+            ColliderView::Ball(s) => (ShapeKind::Ball, vec![s.raw.radius]),
+            ColliderView::Capsule(s) => {
+                let segment = &s.raw.segment;
+                let params = segment.a.iter().chain(segment.b.iter()).copied();
+                (ShapeKind::Capsule, params.chain([s.raw.radius]).collect())
+            }
+            #[cfg(feature = "dim3")]
+            ColliderView::Cylinder(s) => {
+                (ShapeKind::Cylinder, vec![s.raw.half_height, s.raw.radius])
+            }
+            #[cfg(feature = "dim3")]
+            ColliderView::Cone(s) => (ShapeKind::Cone, vec![s.raw.half_height, s.raw.radius]),
+            #[cfg(feature = "dim2")]
+            ColliderView::ConvexPolygon(s) => (
+                ShapeKind::Convex,
+                s.raw
+                    .points()
+                    .iter()
+                    .flat_map(|pt| pt.iter().copied())
+                    .collect(),
+            ),
+            #[cfg(feature = "dim3")]
+            ColliderView::ConvexPolyhedron(s) => (
+                ShapeKind::Convex,
+                s.raw
+                    .points()
+                    .iter()
+                    .flat_map(|pt| pt.iter().copied())
+                    .collect(),
+            ),
+            _ => return None,
+        };
+
+        Some(Self {
+            kind,
+            params: params.into_iter().map(Real::to_bits).collect(),
+        })
+    }
+}
+
+/// Updates the renders of every collider after a change of render mode.
+pub fn refresh_collider_renders(mut renders: Query<&mut ColliderRender>) {
+    for mut render in renders.iter_mut() {
+        render.set_changed();
+    }
 }
 
 pub fn add_collider_render_targets(
@@ -28,44 +188,97 @@ pub fn add_collider_render_targets(
     }
 }
 
-#[derive(Component, Copy, Clone)]
-pub struct RenderInitialized;
+/// The collider shape, surface color, and mesh a render was created with.
+#[derive(Component, Clone)]
+pub struct RenderedCollider {
+    shape: SharedShape,
+    scale: Vect,
+    color: Color,
+    mesh: Option<AssetId<Mesh>>,
+}
+
+impl RenderedCollider {
+    fn new(collider: &Collider, color: Color, mesh: Option<&ColliderRenderMesh>) -> Self {
+        Self {
+            shape: collider.raw.clone(),
+            scale: collider.scale(),
+            color,
+            mesh: mesh.map(|mesh| mesh.0.id()),
+        }
+    }
+
+    /// Is the render outdated?
+    ///
+    /// The collider is flagged as changed whenever its scale is synchronized with its
+    /// transform, so its shape is compared instead. A scaled shape is replaced when the
+    /// scale changes, so any scale change triggers an update.
+    fn is_outdated(
+        &self,
+        collider: &Collider,
+        color: Color,
+        mesh: Option<&ColliderRenderMesh>,
+    ) -> bool {
+        self.color != color
+            || self.mesh != mesh.map(|mesh| mesh.0.id())
+            || self.scale != collider.scale()
+            || !Arc::ptr_eq(&self.shape.0, &collider.raw.0)
+    }
+}
 
 /// System responsible for attaching a PbrBundle to each entity having a collider.
+#[allow(clippy::type_complexity)]
 pub fn create_collider_renders_system(
     mut commands: Commands,
-    cli: Res<CliArgs>,
+    render_mode: Res<RenderMode>,
     mut instances: ResMut<CollisionShapeMeshInstances>,
     mut meshes: ResMut<Assets<Mesh>>,
-    #[cfg(feature = "dim2")] mut materials: ResMut<Assets<ColorMaterial>>,
-    #[cfg(feature = "dim3")] mut materials: ResMut<Assets<StandardMaterial>>,
+    mut materials: ResMut<Assets<ColliderMaterial>>,
     mut coll_shape_render: Query<
         (
             Entity,
             &Collider,
             &ColliderRender,
+            Option<&ColliderStateColor>,
+            Option<&ColliderRenderMesh>,
             &mut ColliderRenderTargets,
+            Option<&RenderedCollider>,
         ),
-        Or<(Changed<ColliderRender>, Without<RenderInitialized>)>,
-        // Or<(Changed<Collider>, Changed<ColliderRender>)>,
+        Or<(
+            Changed<Collider>,
+            Changed<ColliderRender>,
+            Changed<ColliderStateColor>,
+            Changed<ColliderRenderMesh>,
+            Without<RenderedCollider>,
+        )>,
     >,
     existing_entities: Query<Entity>,
-    old_transform: Query<&Transform>, // FIXME: we shouldn’t need this, but right now collider renders get triggered each frame for some reasons.
+    old_transform: Query<&Transform>,
 ) {
-    for (entity, collider, render, mut render_target) in coll_shape_render.iter_mut() {
-        commands.entity(entity).insert(RenderInitialized); // FIXME: not sure what this is needed. Change detection for Changed<ColliderRender> should be enough.
-        if let Some(mesh) =
-            generate_collision_shape_render_mesh(collider, &mut *meshes, &mut instances)
-        {
-            // println!("Rendering with color: {:?}", render.color);
+    for (entity, collider, render, state_color, render_mesh, mut render_target, rendered) in
+        coll_shape_render.iter_mut()
+    {
+        let color = state_color.and_then(|c| c.0).unwrap_or(render.color);
+        let color = render_mode.surface_color(color);
+        if rendered.is_some_and(|rendered| !rendered.is_outdated(collider, color, render_mesh)) {
+            continue;
+        }
 
+        commands
+            .entity(entity)
+            .insert(RenderedCollider::new(collider, color, render_mesh));
+        let mesh = match render_mesh {
+            Some(render_mesh) => Some(render_mesh.0.clone()),
+            None => instances.mesh(collider, &mut meshes),
+        };
+
+        if let Some(mesh) = mesh {
             #[cfg(feature = "dim2")]
             {
                 if let ColliderView::Cuboid(s) = collider.as_unscaled_typed_shape() {
                     #[cfg(feature = "dim2")]
                     let mut bundle = SpriteBundle {
                         sprite: Sprite {
-                            color: render.color.into(),
+                            color,
                             custom_size: Some(Vec2::new(
                                 s.half_extents().x * 2.0,
                                 s.half_extents().y * 2.0,
@@ -94,28 +307,16 @@ pub fn create_collider_renders_system(
             #[cfg(feature = "dim2")]
             let mut bundle = MaterialMesh2dBundle {
                 mesh: mesh.into(),
-                material: materials.add(ColorMaterial::from(render.color)),
+                material: instances.material(color, &mut materials),
                 transform: Transform::from_xyz(0.0, 0.0, (entity.index() + 1) as f32 * 1.0001e-9),
                 ..Default::default()
             };
 
             #[cfg(feature = "dim3")]
-            let mut bundle = {
-                let mut material: StandardMaterial = render.color.into();
-                material.double_sided = true;
-
-                let material_handle = instances
-                    .color_to_material
-                    .iter()
-                    .find(|(c, _)| *c == render.color)
-                    .map(|(_, m)| m.clone())
-                    .unwrap_or_else(|| materials.add(material));
-
-                PbrBundle {
-                    mesh,
-                    material: material_handle,
-                    ..Default::default()
-                }
+            let mut bundle = PbrBundle {
+                mesh,
+                material: instances.material(color, &mut materials),
+                ..Default::default()
             };
 
             if let Some(target) = render_target.target {
@@ -135,33 +336,22 @@ pub fn create_collider_renders_system(
 }
 
 #[cfg(feature = "dim3")]
-fn generate_collision_shape_render_mesh(
-    collider: &Collider,
-    meshes: &mut Assets<Mesh>,
-    instances: &mut CollisionShapeMeshInstances,
-) -> Option<Handle<Mesh>> {
-    const NSUB: u32 = 20;
-
-    let ((vertices, indices), flat_normals) = match collider.as_unscaled_typed_shape() {
-        ColliderView::Cuboid(s) => {
-            if let Some((_, mesh)) = instances
-                .cuboid_to_mesh
-                .iter()
-                .find(|(cuboid, _)| cuboid == s.raw)
-            {
-                return Some(mesh.clone());
-            }
+fn generate_collision_shape_render_mesh(shape: ColliderView) -> Option<Mesh> {
+    let ((vertices, indices), flat_normals) = collision_shape_trimesh(shape)?;
+    Some(gen_bevy_mesh(&vertices, &indices, flat_normals))
+}
 
-            let (vertices, indices) = s.raw.to_trimesh();
-            let mesh = gen_bevy_mesh(&vertices, &indices, true);
-            let handle = meshes.add(mesh);
-            instances
-                .cuboid_to_mesh
-                .push((s.raw.clone(), handle.clone()));
-            return Some(handle);
+/// The triangles used for rendering a shape, and whether they need flat normals.
+#[cfg(feature = "dim3")]
+pub(super) fn collision_shape_trimesh(
+    shape: ColliderView,
+) -> Option<((Vec<Point<Real>>, Vec<[u32; 3]>), bool)> {
+    const NSUB: u32 = 20;
+    // The radius of the capsules used to render segments and polylines.
+    const LINE_RADIUS: Real = 0.025;
 
-            // (s.raw.to_trimesh(), true)
-        }
+    let result = match shape {
+        ColliderView::Cuboid(s) => (s.raw.to_trimesh(), true),
         ColliderView::Ball(s) => (s.raw.to_trimesh(NSUB, NSUB / 2), false),
         ColliderView::Cylinder(s) => {
             let (mut vtx, mut idx) = s.raw.to_trimesh(NSUB);
@@ -199,10 +389,34 @@ fn generate_collision_shape_render_mesh(
         }
         ColliderView::Capsule(s) => (s.raw.to_trimesh(NSUB, NSUB / 2), false),
         ColliderView::ConvexPolyhedron(s) => (s.raw.to_trimesh(), true),
-        // ColliderView::Compound(s) => s.raw.to_trimesh(),
+        ColliderView::Compound(s) => {
+            let parts = s.raw.shapes().iter().filter_map(|(pos, part)| {
+                let ((vtx, idx), flat_normals) =
+                    collision_shape_trimesh(part.as_typed_shape().into())?;
+                // Flat parts get their own vertices for each triangle, so the smooth normals
+                // computed for the whole compound are flat on them.
+                let (vtx, idx) = if flat_normals {
+                    unshare_vertices(&vtx, &idx)
+                } else {
+                    (vtx, idx)
+                };
+                Some((vtx.iter().map(|pt| pos * pt).collect(), idx))
+            });
+
+            (merge_trimeshes(parts), false)
+        }
         ColliderView::HeightField(s) => (s.raw.to_trimesh(), true),
-        // ColliderView::Polyline(s) => s.raw.to_trimesh(),
-        // ColliderView::Triangle(s) => s.raw.to_trimesh(),
+        ColliderView::Polyline(s) => {
+            let segments = s.raw.segments().map(|segment| {
+                Capsule::new(segment.a, segment.b, LINE_RADIUS).to_trimesh(NSUB / 2, NSUB / 4)
+            });
+            (merge_trimeshes(segments), false)
+        }
+        ColliderView::Segment(s) => (
+            Capsule::new(s.raw.a, s.raw.b, LINE_RADIUS).to_trimesh(NSUB / 2, NSUB / 4),
+            false,
+        ),
+        ColliderView::Triangle(s) => ((vec![s.raw.a, s.raw.b, s.raw.c], vec![[0, 1, 2]]), true),
         ColliderView::HalfSpace(s) => {
             let normal = s.normal();
             let extent = 100.0;
@@ -220,36 +434,210 @@ fn generate_collision_shape_render_mesh(
         ColliderView::TriMesh(s) => ((s.raw.vertices().to_vec(), s.indices().to_vec()), true),
         #[cfg(feature = "voxels")]
         ColliderView::Voxels(s) => (s.raw.to_trimesh(), true),
-        _ => todo!(),
+        ColliderView::RoundCuboid(s) => (
+            rounded_trimesh(&s.raw.inner_shape.to_trimesh().0, s.raw.border_radius)?,
+            false,
+        ),
+        ColliderView::RoundTriangle(s) => {
+            let triangle = &s.raw.inner_shape;
+            (
+                rounded_trimesh(&[triangle.a, triangle.b, triangle.c], s.raw.border_radius)?,
+                false,
+            )
+        }
+        ColliderView::RoundCylinder(s) => (
+            rounded_trimesh(&s.raw.inner_shape.to_trimesh(NSUB).0, s.raw.border_radius)?,
+            false,
+        ),
+        ColliderView::RoundCone(s) => (
+            rounded_trimesh(&s.raw.inner_shape.to_trimesh(NSUB).0, s.raw.border_radius)?,
+            false,
+        ),
+        ColliderView::RoundConvexPolyhedron(s) => (
+            rounded_trimesh(s.raw.inner_shape.points(), s.raw.border_radius)?,
+            false,
+        ),
     };
 
-    let mesh = gen_bevy_mesh(&vertices, &indices, flat_normals);
-    Some(meshes.add(mesh))
+    Some(result)
+}
+
+/// Merges several triangle meshes into a single one.
+#[cfg(feature = "dim3")]
+fn merge_trimeshes(
+    parts: impl IntoIterator<Item = (Vec<Point<Real>>, Vec<[u32; 3]>)>,
+) -> (Vec<Point<Real>>, Vec<[u32; 3]>) {
+    let mut vertices = vec![];
+    let mut indices = vec![];
+
+    for (part_vtx, part_idx) in parts {
+        let base_id = vertices.len() as u32;
+        vertices.extend(part_vtx);
+        indices.extend(
+            part_idx
+                .iter()
+                .map(|idx| [idx[0] + base_id, idx[1] + base_id, idx[2] + base_id]),
+        );
+    }
+
+    (vertices, indices)
+}
+
+/// Gives each triangle its own three vertices.
+#[cfg(feature = "dim3")]
+fn unshare_vertices(
+    vertices: &[Point<Real>],
+    indices: &[[u32; 3]],
+) -> (Vec<Point<Real>>, Vec<[u32; 3]>) {
+    let vertices = indices
+        .iter()
+        .flat_map(|idx| idx.map(|i| vertices[i as usize]))
+        .collect();
+    let indices = (0..indices.len() as u32)
+        .map(|i| [i * 3, i * 3 + 1, i * 3 + 2])
+        .collect();
+    (vertices, indices)
+}
+
+/// The triangles of a convex shape with the given vertices, dilated by `border_radius`.
+#[cfg(feature = "dim3")]
+fn rounded_trimesh(
+    points: &[Point<Real>],
+    border_radius: Real,
+) -> Option<(Vec<Point<Real>>, Vec<[u32; 3]>)> {
+    const NSUB: u32 = 8;
+
+    let (sphere, _) = Ball::new(border_radius).to_trimesh(NSUB, NSUB / 2);
+    let dilated: Vec<_> = points
+        .iter()
+        .flat_map(|pt| sphere.iter().map(move |s| pt + s.coords))
+        .collect();
+    try_convex_hull(&dilated).ok()
 }
 
 #[cfg(feature = "dim2")]
-fn generate_collision_shape_render_mesh(
-    collider: &Collider,
-    meshes: &mut Assets<Mesh>,
-    _unused: &mut CollisionShapeMeshInstances,
-) -> Option<Handle<Mesh>> {
+fn generate_collision_shape_render_mesh(shape: ColliderView) -> Option<Mesh> {
+    let (vertices, indices) = collision_shape_triangles(shape)?;
+    Some(gen_bevy_mesh(&vertices, indices))
+}
+
+/// The triangles used for rendering a shape.
+///
+/// If there are no indices, the vertices form a convex polygon.
+#[cfg(feature = "dim2")]
+fn collision_shape_triangles(
+    shape: ColliderView,
+) -> Option<(Vec<Point<Real>>, Option<Vec<[u32; 3]>>)> {
     const NSUB: u32 = 20;
 
-    let (vertices, indices) = match collider.as_unscaled_typed_shape() {
+    let result = match shape {
         ColliderView::Cuboid(s) => (s.raw.to_polyline(), None),
         ColliderView::Ball(s) => (s.raw.to_polyline(NSUB), None),
         ColliderView::Capsule(s) => (s.raw.to_polyline(NSUB), None),
-        // ColliderView::ConvexPolygon(s) => (s.raw.to_polyline(), None),
-        // ColliderView::Compound(s) => s.raw.to_polyline(),
-        ColliderView::HeightField(s) => return None, // (s.raw.to_polyline(), None),
-        // ColliderView::Polyline(s) => s.raw.to_polyline(),
-        // ColliderView::Triangle(s) => s.raw.to_polyline(),
+        ColliderView::ConvexPolygon(s) => (s.raw.points().to_vec(), None),
+        ColliderView::Compound(s) => {
+            let mut vertices = vec![];
+            let mut indices = vec![];
+
+            for (pos, part) in s.raw.shapes() {
+                let (part_vtx, part_idx) = collision_shape_triangles(part.as_typed_shape().into())?;
+                let part_idx = part_idx.unwrap_or_else(|| polygon_fan(part_vtx.len()));
+                let base_id = vertices.len() as u32;
+                vertices.extend(part_vtx.iter().map(|pt| pos * pt));
+                indices.extend(
+                    part_idx
+                        .iter()
+                        .map(|idx| [idx[0] + base_id, idx[1] + base_id, idx[2] + base_id]),
+                );
+            }
+
+            (vertices, Some(indices))
+        }
+        ColliderView::HeightField(s) => segments_triangles(s.raw.segments()),
+        ColliderView::Polyline(s) => segments_triangles(s.raw.segments()),
+        ColliderView::Segment(s) => segments_triangles([*s.raw]),
+        ColliderView::Triangle(s) => (vec![s.raw.a, s.raw.b, s.raw.c], None),
+        ColliderView::HalfSpace(s) => {
+            let extent = 100.0;
+            let normal = *s.raw.normal * extent;
+            let tangent = Vector::new(-normal.y, normal.x);
+            let vertices = vec![
+                Point::from(tangent),
+                Point::from(-tangent),
+                Point::from(-tangent - normal),
+                Point::from(tangent - normal),
+            ];
+            (vertices, None)
+        }
+        ColliderView::RoundCuboid(s) => (
+            rounded_polygon(&s.raw.inner_shape.to_polyline(), s.raw.border_radius),
+            None,
+        ),
+        ColliderView::RoundTriangle(s) => {
+            let triangle = &s.raw.inner_shape;
+            (
+                rounded_polygon(&[triangle.a, triangle.b, triangle.c], s.raw.border_radius),
+                None,
+            )
+        }
+        ColliderView::RoundConvexPolygon(s) => (
+            rounded_polygon(s.raw.inner_shape.points(), s.raw.border_radius),
+            None,
+        ),
         ColliderView::TriMesh(s) => (s.raw.vertices().to_vec(), Some(s.indices().to_vec())),
-        _ => todo!(),
     };
 
-    let mesh = gen_bevy_mesh(&vertices, indices);
-    Some(meshes.add(mesh))
+    Some(result)
+}
+
+/// Renders each segment as a thin rectangle.
+#[cfg(feature = "dim2")]
+fn segments_triangles(
+    segments: impl IntoIterator<Item = Segment>,
+) -> (Vec<Point<Real>>, Option<Vec<[u32; 3]>>) {
+    const LINE_WIDTH: Real = 0.05;
+
+    let mut vertices = vec![];
+    let mut indices = vec![];
+
+    for segment in segments {
+        let Some(dir) = segment.direction() else {
+            continue;
+        };
+        let offset = Vector::new(-dir.y, dir.x) * LINE_WIDTH / 2.0;
+        let base_id = vertices.len() as u32;
+        vertices.extend([
+            segment.a - offset,
+            segment.b - offset,
+            segment.b + offset,
+            segment.a + offset,
+        ]);
+        indices.push([base_id, base_id + 1, base_id + 2]);
+        indices.push([base_id, base_id + 2, base_id + 3]);
+    }
+
+    (vertices, Some(indices))
+}
+
+/// The vertices of a convex polygon with the given vertices, dilated by `border_radius`.
+#[cfg(feature = "dim2")]
+fn rounded_polygon(points: &[Point<Real>], border_radius: Real) -> Vec<Point<Real>> {
+    const NSUB: u32 = 8;
+
+    let circle = Ball::new(border_radius).to_polyline(NSUB);
+    let dilated: Vec<_> = points
+        .iter()
+        .flat_map(|pt| circle.iter().map(move |c| pt + c.coords))
+        .collect();
+    convex_hull(&dilated)
+}
+
+/// Triangulates a convex polygon with `num_vertices` vertices.
+#[cfg(feature = "dim2")]
+fn polygon_fan(num_vertices: usize) -> Vec<[u32; 3]> {
+    (1..num_vertices.max(2) as u32 - 1)
+        .map(|i| [0, i, i + 1])
+        .collect()
 }
 
 #[cfg(feature = "dim2")]
@@ -269,11 +657,7 @@ fn gen_bevy_mesh(vertices: &[Point<Real>], mut indices: Option<Vec<[u32; 3]>>) -
     );
 
     if indices.is_none() {
-        indices = Some(
-            (1..vertices.len() as u32 - 1)
-                .map(|i| [0, i, i + 1])
-                .collect(),
-        );
+        indices = Some(polygon_fan(vertices.len()));
     }
 
     mesh.insert_indices(Indices::U32(
@@ -46,6 +46,15 @@ impl ColliderRender {
     }
 }
 
+/// The color replacing the color of a collider while the colliders are colored by simulation
+/// state.
+#[derive(Copy, Clone, Component, Default, PartialEq)]
+pub struct ColliderStateColor(pub Option<Color>);
+
+/// A mesh rendered instead of the one generated from the collider shape.
+#[derive(Clone, Component)]
+pub struct ColliderRenderMesh(pub Handle<Mesh>);
+
 #[derive(Copy, Clone, Component, Default)]
 pub struct ColliderRenderTargets {
     pub target: Option<Entity>,
@@ -55,9 +64,18 @@ pub struct ColliderRenderTargets {
 /*
  * Shape outline rendering.
  */
+#[cfg(feature = "dim3")]
+pub const SELECTION_OUTLINE_COLOR: Color = Color::srgb(1.0, 0.6, 0.0);
+#[cfg(feature = "dim3")]
+pub const HOVER_OUTLINE_COLOR: Color = Color::srgb(1.0, 1.0, 0.6);
+/// The width, in pixels, of the outlines of the selected and hovered colliders.
+#[cfg(feature = "dim3")]
+pub const HIGHLIGHT_OUTLINE_WIDTH: f32 = 3.0;
+
 #[derive(Copy, Clone, Component)]
 pub struct ColliderOutlineRender {
     pub color: Color,
+    /// The width of the outline, in pixels.
     pub thickness: f32,
 }
 
@@ -88,6 +106,52 @@ impl ColliderOutlineRender {
     }
 }
 
+/*
+ * Render modes.
+ */
+/// How the colliders are rendered.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+pub enum RenderMode {
+    #[default]
+    Shaded,
+    /// Shaded surfaces with the edges of the shapes on top.
+    ShadedOutline,
+    /// Only the edges of the shapes.
+    Wireframe,
+    /// Transparent surfaces, showing the shapes behind them.
+    XRay,
+}
+
+impl RenderMode {
+    #[cfg(feature = "dim2")]
+    pub const ALL: [(Self, &'static str); 2] = [(Self::Shaded, "Shaded"), (Self::XRay, "X-ray")];
+    #[cfg(feature = "dim3")]
+    pub const ALL: [(Self, &'static str); 4] = [
+        (Self::Shaded, "Shaded"),
+        (Self::ShadedOutline, "Shaded + outline"),
+        (Self::Wireframe, "Wireframe"),
+        (Self::XRay, "X-ray"),
+    ];
+
+    /// The color of the surface of a collider rendered with the given color.
+    pub fn surface_color(self, color: Color) -> Color {
+        match self {
+            Self::XRay => color.with_alpha(0.25),
+            _ => color,
+        }
+    }
+
+    #[cfg(feature = "dim3")]
+    pub fn shows_surfaces(self) -> bool {
+        self != Self::Wireframe
+    }
+
+    #[cfg(feature = "dim3")]
+    pub fn shows_outlines(self) -> bool {
+        self != Self::Shaded
+    }
+}
+
 /*
  * Joint rendering.
  */
@@ -1,16 +1,35 @@
-use crate::render::{ColliderOutlineRender, JointRender};
+use crate::render::JointRender;
 use bevy::prelude::*;
-use bevy::render::mesh::{Indices, VertexAttributeValues};
-use bevy_prototype_debug_lines::DebugLines;
-use bevy_rapier::geometry::Collider;
-use bevy_rapier::plugin::{RapierConfiguration, RapierContext};
-use bevy_rapier::prelude::shape_views::ColliderView;
+use bevy_rapier::dynamics::{ImpulseJoint, MultibodyJoint};
+use bevy_rapier::math::Vect;
+use bevy_rapier::plugin::RapierContext;
 use bevy_rapier::prelude::{RapierImpulseJointHandle, RapierMultibodyJointHandle};
-use bevy_rapier::rapier::dynamics::GenericJoint;
-use bevy_rapier::rapier::math::{Point, Real, Vector};
+use bevy_rapier::rapier::dynamics::{GenericJoint, JointAxesMask, JointAxis};
+use bevy_rapier::rapier::math::{Point, Real};
+
+/// Radius of the arcs showing the limits of revolute joints.
+const LIMIT_ARC_RADIUS: Real = 0.25;
+/// Number of segments of the arcs showing the limits of revolute joints.
+const LIMIT_ARC_SUBDIVS: usize = 16;
+
+/// Adds the default `JointRender` to joints that don’t have one.
+pub fn add_joint_renders(
+    mut commands: Commands,
+    missing_renders: Query<
+        Entity,
+        (
+            Or<(With<ImpulseJoint>, With<MultibodyJoint>)>,
+            Without<JointRender>,
+        ),
+    >,
+) {
+    for entity in missing_renders.iter() {
+        commands.entity(entity).insert(JointRender::default());
+    }
+}
 
 pub fn render_joints(
-    mut lines: ResMut<DebugLines>,
+    mut gizmos: Gizmos,
     context: Res<RapierContext>,
     impulse_joint_render: Query<(&JointRender, &RapierImpulseJointHandle)>,
     multibody_joint_render: Query<(&JointRender, &RapierMultibodyJointHandle)>,
@@ -20,25 +39,39 @@ pub fn render_joints(
             let frame1 = rb1.position() * data.local_frame1;
             let frame2 = rb2.position() * data.local_frame2;
 
-            #[cfg(feature = "dim2")]
-            {
-                let a = rb1.translation().push(0.0);
-                let b = frame1.translation.vector.push(0.0);
-                let c = frame2.translation.vector.push(0.0);
-                let d = rb2.translation().push(0.0);
-                lines.line_colored(a.into(), b.into(), 0.0, render.anchor_color);
-                lines.line_colored(b.into(), c.into(), 0.0, render.separation_color);
-                lines.line_colored(c.into(), d.into(), 0.0, render.anchor_color);
-            }
-            #[cfg(feature = "dim3")]
-            {
-                let a = *rb1.translation();
-                let b = frame1.translation.vector;
-                let c = frame2.translation.vector;
-                let d = *rb2.translation();
-                lines.line_colored(a.into(), b.into(), 0.0, render.anchor_color);
-                lines.line_colored(b.into(), c.into(), 0.0, render.separation_color);
-                lines.line_colored(c.into(), d.into(), 0.0, render.anchor_color);
+            let a = Vect::from(*rb1.translation());
+            let b = Vect::from(frame1.translation.vector);
+            let c = Vect::from(frame2.translation.vector);
+            let d = Vect::from(*rb2.translation());
+            line(&mut gizmos, a, b, render.anchor_color);
+            line(&mut gizmos, b, c, render.separation_color);
+            line(&mut gizmos, c, d, render.anchor_color);
+
+            if data.locked_axes == JointAxesMask::LOCKED_REVOLUTE_AXES {
+                // The revolute axis is the X axis of the joint frames, and the rotation
+                // angle is measured from their Y axis (in 3D) or X axis (in 2D).
+                #[cfg(feature = "dim2")]
+                let arc_point = |angle: Real| Point::new(angle.cos(), angle.sin());
+                #[cfg(feature = "dim3")]
+                let arc_point = |angle: Real| Point::new(0.0, angle.cos(), angle.sin());
+
+                if let Some(limits) = data.limits(JointAxis::AngX) {
+                    let arc: Vec<_> = (0..=LIMIT_ARC_SUBDIVS)
+                        .map(|i| {
+                            let t = i as Real / LIMIT_ARC_SUBDIVS as Real;
+                            let angle = limits.min + (limits.max - limits.min) * t;
+                            to_vect(&(frame1 * (arc_point(angle) * LIMIT_ARC_RADIUS)))
+                        })
+                        .collect();
+                    let (first, last) = (arc[0], arc[arc.len() - 1]);
+                    linestrip(&mut gizmos, arc, render.separation_color);
+                    line(&mut gizmos, b, first, render.separation_color);
+                    line(&mut gizmos, b, last, render.separation_color);
+                }
+
+                // The current angle of the joint.
+                let current = to_vect(&(frame2 * (arc_point(0.0) * LIMIT_ARC_RADIUS)));
+                line(&mut gizmos, c, current, render.anchor_color);
             }
         }
     };
@@ -52,7 +85,9 @@ pub fn render_joints(
     for (render, handle) in multibody_joint_render.iter() {
         if let Some((multibody, id)) = context.multibody_joints.get(handle.0) {
             let link = multibody.link(id).unwrap();
-            let parent = multibody.link(link.parent_id().unwrap()).unwrap();
+            let Some(parent) = link.parent_id().and_then(|id| multibody.link(id)) else {
+                continue;
+            };
             render_joint(
                 parent.rigid_body_handle(),
                 link.rigid_body_handle(),
@@ -62,3 +97,27 @@ pub fn render_joints(
         }
     }
 }
+
+pub(super) fn to_vect(pt: &Point<Real>) -> Vect {
+    Vect::from(pt.coords)
+}
+
+#[cfg(feature = "dim2")]
+fn line(gizmos: &mut Gizmos, a: Vect, b: Vect, color: Color) {
+    gizmos.line_2d(a, b, color);
+}
+
+#[cfg(feature = "dim3")]
+fn line(gizmos: &mut Gizmos, a: Vect, b: Vect, color: Color) {
+    gizmos.line(a, b, color);
+}
+
+#[cfg(feature = "dim2")]
+pub(super) fn linestrip(gizmos: &mut Gizmos, points: Vec<Vect>, color: Color) {
+    gizmos.linestrip_2d(points, color);
+}
+
+#[cfg(feature = "dim3")]
+pub(super) fn linestrip(gizmos: &mut Gizmos, points: Vec<Vect>, color: Color) {
+    gizmos.linestrip(points, color);
+}
@@ -1,19 +1,23 @@
 pub use self::add_missing_transforms::*;
 // #[cfg(feature = "dim2")]
 // pub use self::collision_shape_outline_render2d::*;
-// #[cfg(feature = "dim3")]
-// pub use self::collision_shape_outline_render3d::*;
+#[cfg(feature = "dim3")]
+pub use self::collision_shape_outline_render3d::*;
 pub use self::collision_shape_render::*;
 pub use self::components::*;
-// pub use self::joint_render::*;
+pub use self::joint_render::*;
+pub use self::physics_overlays::*;
 pub use self::plugins::*;
+pub use self::state_coloring::*;
 
 mod add_missing_transforms;
 // #[cfg(feature = "dim2")]
 // mod collision_shape_outline_render2d;
-// #[cfg(feature = "dim3")]
-// mod collision_shape_outline_render3d;
+#[cfg(feature = "dim3")]
+mod collision_shape_outline_render3d;
 mod collision_shape_render;
 mod components;
-// mod joint_render;
+mod joint_render;
+mod physics_overlays;
 mod plugins;
+mod state_coloring;
@@ -0,0 +1,244 @@
+use crate::render::joint_render::{linestrip, to_vect};
+use bevy::prelude::*;
+use bevy_rapier::dynamics::{ExternalForce, ReadMassProperties};
+use bevy_rapier::math::Vect;
+use bevy_rapier::plugin::RapierContext;
+use bevy_rapier::prelude::RapierRigidBodyHandle;
+use bevy_rapier::rapier::dynamics::RigidBody;
+use bevy_rapier::rapier::math::{AngVector, Real};
+#[cfg(feature = "dim3")]
+use bevy_rapier::utils::iso_to_transform;
+
+const LINEAR_VELOCITY_COLOR: Color = Color::srgb(0.2, 0.8, 1.0);
+const ANGULAR_VELOCITY_COLOR: Color = Color::srgb(0.8, 0.4, 1.0);
+const EXTERNAL_FORCE_COLOR: Color = Color::srgb(1.0, 0.3, 0.2);
+const CONTACT_FORCE_COLOR: Color = Color::srgb(1.0, 0.8, 0.1);
+const CENTER_OF_MASS_COLOR: Color = Color::srgb(1.0, 1.0, 1.0);
+/// The colors of the principal inertia axes.
+#[cfg(feature = "dim3")]
+const INERTIA_AXES_COLORS: [Color; 3] = [
+    Color::srgb(1.0, 0.2, 0.2),
+    Color::srgb(0.2, 1.0, 0.2),
+    Color::srgb(0.2, 0.4, 1.0),
+];
+/// Radius of the arcs showing the angular velocities.
+const ANGULAR_VELOCITY_ARC_RADIUS: Real = 0.5;
+/// Number of segments of the arcs showing the angular velocities.
+const ANGULAR_VELOCITY_ARC_SUBDIVS: usize = 24;
+/// Radius of the marker of the centers of mass.
+const CENTER_OF_MASS_RADIUS: Real = 0.05;
+
+bitflags::bitflags! {
+    /// The overlays showing the state of the rigid-bodies.
+    #[derive(Default)]
+    pub struct PhysicsOverlayMode: u32 {
+        const LINEAR_VELOCITIES = 1 << 0;
+        const ANGULAR_VELOCITIES = 1 << 1;
+        const EXTERNAL_FORCES = 1 << 2;
+        const CONTACT_FORCES = 1 << 3;
+        const MASS_PROPERTIES = 1 << 4;
+    }
+}
+
+/// The overlays drawn on top of the rigid-bodies, and the length of their arrows.
+#[derive(Resource)]
+pub struct PhysicsOverlays {
+    pub mode: PhysicsOverlayMode,
+    /// The length of the velocity arrows for a velocity of 1 m/s.
+    pub velocity_scale: Real,
+    /// The length of the force arrows for a force of 1 N.
+    pub force_scale: Real,
+}
+
+impl Default for PhysicsOverlays {
+    fn default() -> Self {
+        Self {
+            mode: PhysicsOverlayMode::empty(),
+            velocity_scale: 0.5,
+            force_scale: 0.1,
+        }
+    }
+}
+
+pub fn render_physics_overlays(
+    mut gizmos: Gizmos,
+    overlays: Res<PhysicsOverlays>,
+    context: Res<RapierContext>,
+    bodies: Query<(
+        &RapierRigidBodyHandle,
+        Option<&ExternalForce>,
+        Option<&ReadMassProperties>,
+    )>,
+) {
+    let mode = overlays.mode;
+    if mode.is_empty() {
+        return;
+    }
+
+    for (handle, force, mprops) in bodies.iter() {
+        let Some(rb) = context.bodies.get(handle.0) else {
+            continue;
+        };
+        let center = to_vect(rb.center_of_mass());
+
+        if mode.contains(PhysicsOverlayMode::LINEAR_VELOCITIES) {
+            let linvel = Vect::from(*rb.linvel()) * overlays.velocity_scale;
+            arrow(&mut gizmos, center, center + linvel, LINEAR_VELOCITY_COLOR);
+        }
+
+        if mode.contains(PhysicsOverlayMode::ANGULAR_VELOCITIES) {
+            angular_velocity_arc(
+                &mut gizmos,
+                center,
+                rb.vels().angvel,
+                overlays.velocity_scale,
+            );
+        }
+
+        if mode.contains(PhysicsOverlayMode::EXTERNAL_FORCES) {
+            if let Some(force) = force {
+                let end = center + force.force * overlays.force_scale;
+                arrow(&mut gizmos, center, end, EXTERNAL_FORCE_COLOR);
+            }
+        }
+
+        if mode.contains(PhysicsOverlayMode::MASS_PROPERTIES) {
+            if let Some(mprops) = mprops {
+                mass_properties(&mut gizmos, rb, mprops);
+            }
+        }
+    }
+
+    if mode.contains(PhysicsOverlayMode::CONTACT_FORCES) {
+        let inv_dt = context.integration_parameters.inv_dt();
+
+        for pair in context.narrow_phase.contact_pairs() {
+            if !pair.has_any_active_contact {
+                continue;
+            }
+            let Some(co1) = context.colliders.get(pair.collider1) else {
+                continue;
+            };
+
+            // The normals point toward the second collider, which is pushed along them.
+            for manifold in &pair.manifolds {
+                let normal = Vect::from(manifold.data.normal);
+                for contact in &manifold.points {
+                    if contact.data.impulse == 0.0 {
+                        continue;
+                    }
+                    let point = to_vect(&(co1.position() * contact.local_p1));
+                    let force = normal * contact.data.impulse * inv_dt;
+                    arrow(
+                        &mut gizmos,
+                        point,
+                        point + force * overlays.force_scale,
+                        CONTACT_FORCE_COLOR,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// An arc around the center of mass, as long as the angle covered in `scale` seconds.
+#[cfg(feature = "dim2")]
+fn angular_velocity_arc(gizmos: &mut Gizmos, center: Vect, angvel: AngVector<Real>, scale: Real) {
+    let angle = angvel * scale;
+    let arc_point =
+        |angle: Real| center + Vect::new(angle.cos(), angle.sin()) * ANGULAR_VELOCITY_ARC_RADIUS;
+    draw_arc(gizmos, angle, arc_point);
+}
+
+/// An arc around the rotation axis through the center of mass, as long as the angle covered
+/// in `scale` seconds.
+#[cfg(feature = "dim3")]
+fn angular_velocity_arc(gizmos: &mut Gizmos, center: Vect, angvel: AngVector<Real>, scale: Real) {
+    let angvel = Vect::from(angvel);
+    let Some(axis) = angvel.try_normalize() else {
+        return;
+    };
+    let (u, v) = axis.any_orthonormal_pair();
+    gizmos.line(
+        center - axis * ANGULAR_VELOCITY_ARC_RADIUS,
+        center + axis * ANGULAR_VELOCITY_ARC_RADIUS,
+        ANGULAR_VELOCITY_COLOR,
+    );
+    let arc_point =
+        |angle: Real| center + (u * angle.cos() + v * angle.sin()) * ANGULAR_VELOCITY_ARC_RADIUS;
+    draw_arc(gizmos, angvel.length() * scale, arc_point);
+}
+
+/// Draws an arc with an arrow tip, from the angle 0 to `angle`.
+fn draw_arc(gizmos: &mut Gizmos, angle: Real, arc_point: impl Fn(Real) -> Vect) {
+    // Keep a gap between both ends of the arc, so that its direction stays readable.
+    let max_angle = 1.9 * std::f32::consts::PI;
+    let angle = angle.clamp(-max_angle, max_angle);
+    if angle.abs() < 1.0e-3 {
+        return;
+    }
+
+    let arc: Vec<_> = (0..=ANGULAR_VELOCITY_ARC_SUBDIVS)
+        .map(|i| arc_point(angle * i as Real / ANGULAR_VELOCITY_ARC_SUBDIVS as Real))
+        .collect();
+    let (before_last, last) = (arc[arc.len() - 2], arc[arc.len() - 1]);
+    linestrip(gizmos, arc, ANGULAR_VELOCITY_COLOR);
+    arrow(gizmos, before_last, last, ANGULAR_VELOCITY_COLOR);
+}
+
+/// The center of mass, and the principal inertia axes (in 3D) or the radius of gyration (in
+/// 2D).
+///
+/// The principal axes are as long as the radius of gyration around them, i.e., the distance
+/// from the rotation axis at which the whole mass would have the same inertia.
+fn mass_properties(gizmos: &mut Gizmos, rb: &RigidBody, mprops: &ReadMassProperties) {
+    let mprops = mprops.get();
+    let center = to_vect(rb.center_of_mass());
+    let gyration_radius = |inertia: Real| {
+        if mprops.mass > 0.0 {
+            (inertia / mprops.mass).sqrt()
+        } else {
+            0.0
+        }
+    };
+
+    #[cfg(feature = "dim2")]
+    {
+        gizmos.circle_2d(center, CENTER_OF_MASS_RADIUS, CENTER_OF_MASS_COLOR);
+        let radius = gyration_radius(mprops.principal_inertia);
+        if radius > 0.0 {
+            gizmos.circle_2d(center, radius, CENTER_OF_MASS_COLOR);
+        }
+    }
+
+    #[cfg(feature = "dim3")]
+    {
+        gizmos.sphere(
+            center,
+            Quat::IDENTITY,
+            CENTER_OF_MASS_RADIUS,
+            CENTER_OF_MASS_COLOR,
+        );
+        let rotation =
+            iso_to_transform(rb.position()).rotation * mprops.principal_inertia_local_frame;
+        let inertia = mprops.principal_inertia.to_array();
+        for (k, (axis, color)) in [Vect::X, Vect::Y, Vect::Z]
+            .into_iter()
+            .zip(INERTIA_AXES_COLORS)
+            .enumerate()
+        {
+            let half_axis = rotation * axis * gyration_radius(inertia[k]);
+            gizmos.line(center - half_axis, center + half_axis, color);
+        }
+    }
+}
+
+#[cfg(feature = "dim2")]
+fn arrow(gizmos: &mut Gizmos, a: Vect, b: Vect, color: Color) {
+    gizmos.arrow_2d(a, b, color);
+}
+
+#[cfg(feature = "dim3")]
+fn arrow(gizmos: &mut Gizmos, a: Vect, b: Vect, color: Color) {
+    gizmos.arrow(a, b, color);
+}
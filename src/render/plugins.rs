@@ -1,4 +1,7 @@
-use crate::render::{add_collider_render_targets, CollisionShapeMeshInstances};
+use crate::render::{
+    add_collider_render_targets, refresh_collider_renders, update_state_colors,
+    CollisionShapeMeshInstances, PhysicsOverlays, RenderMode, StateColoring,
+};
 use crate::SteadyumStages;
 use bevy::ecs::prelude::apply_deferred;
 use bevy::prelude::*;
@@ -37,6 +40,19 @@ impl Plugin for RapierRenderPlugin {
         // );
 
         app.init_resource::<CollisionShapeMeshInstances>()
+            .init_resource::<RenderMode>()
+            .init_resource::<StateColoring>()
+            .init_resource::<PhysicsOverlays>()
+            .add_systems(
+                Update,
+                update_state_colors.in_set(RenderSystems::AddMissingTransforms),
+            )
+            .add_systems(
+                Update,
+                refresh_collider_renders
+                    .run_if(resource_changed::<RenderMode>)
+                    .before(RenderSystems::CreateColliderRenders),
+            )
             .add_systems(
                 Update,
                 apply_deferred
@@ -54,16 +70,33 @@ impl Plugin for RapierRenderPlugin {
             .add_systems(
                 Update, // SteadyumStages::RenderStage,
                 super::add_missing_transforms.in_set(RenderSystems::AddMissingTransforms),
+            )
+            .add_systems(
+                Update,
+                super::add_joint_renders.in_set(RenderSystems::AddMissingTransforms),
+            )
+            .add_systems(
+                Update,
+                super::render_joints.in_set(RenderSystems::RenderJoints),
+            )
+            .add_systems(
+                Update,
+                super::render_physics_overlays.in_set(RenderSystems::RenderJoints),
             );
-        // .add_systems(
-        //     SteadyumStages::RenderStage,
-        //     super::create_collider_outline_renders_system
-        //         .label(RenderSystems::CreateColliderOutlineRenders),
-        // );
-        // .add_systems(
-        //     CoreStage::Update,
-        //     super::render_joints.label(RenderSystems::RenderJoints),
-        // );
+
+        #[cfg(feature = "dim3")]
+        {
+            app.init_resource::<super::CollisionShapeOutlineInstances>()
+                .add_systems(
+                    Update,
+                    (
+                        super::create_collider_outline_renders_system,
+                        super::update_collider_outlines,
+                    )
+                        .chain()
+                        .in_set(RenderSystems::CreateColliderOutlineRenders),
+                );
+        }
 
         #[cfg(feature = "dim2")]
         {
@@ -0,0 +1,358 @@
+use crate::render::{ColliderRender, ColliderStateColor};
+use crate::styling::ColorGenerator;
+use bevy::prelude::*;
+use bevy_egui::egui::ahash::{HashMap, HashSet};
+use bevy_rapier::plugin::RapierContext;
+use bevy_rapier::rapier::dynamics::{RigidBody, RigidBodyHandle, RigidBodyType};
+use bevy_rapier::rapier::geometry::Collider as RapierCollider;
+use bevy_rapier::rapier::math::Real;
+
+/// The color of the colliders a mode doesn’t apply to, like fixed bodies for speeds.
+const INACTIVE_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
+/// The number of colors of the heat-maps.
+const HEAT_STEPS: Real = 16.0;
+/// The number of islands listed in the legend.
+const MAX_LEGEND_ISLANDS: usize = 10;
+
+/// What the colors of the colliders represent.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ColorMode {
+    /// The colors of the colliders.
+    #[default]
+    Default,
+    Sleeping,
+    /// Bodies touching each other, or attached by joints, share the same color.
+    Island,
+    BodyType,
+    /// A heat-map of the linear speed.
+    Speed,
+    /// A heat-map of the kinetic energy.
+    KineticEnergy,
+    /// The first collision group the colliders are member of.
+    CollisionGroup,
+}
+
+impl ColorMode {
+    pub const ALL: [(Self, &'static str); 7] = [
+        (Self::Default, "Default"),
+        (Self::Sleeping, "Sleeping"),
+        (Self::Island, "Island"),
+        (Self::BodyType, "Body type"),
+        (Self::Speed, "Speed"),
+        (Self::KineticEnergy, "Kinetic energy"),
+        (Self::CollisionGroup, "Collision group"),
+    ];
+}
+
+/// The coloring of the colliders by simulation state.
+#[derive(Resource)]
+pub struct StateColoring {
+    pub mode: ColorMode,
+    /// The speed colored with the hottest color of the heat-map.
+    pub max_speed: Real,
+    /// The kinetic energy colored with the hottest color of the heat-map.
+    pub max_kinetic_energy: Real,
+    /// The meaning of the colors of the current mode.
+    pub legend: Vec<(Color, String)>,
+}
+
+impl Default for StateColoring {
+    fn default() -> Self {
+        Self {
+            mode: ColorMode::Default,
+            max_speed: 10.0,
+            max_kinetic_energy: 100.0,
+            legend: vec![],
+        }
+    }
+}
+
+/// A color from blue (cold) to red (hot), for a value between 0 and 1.
+///
+/// The values are rounded, so that only a few materials are needed.
+pub fn heat_color(t: Real) -> Color {
+    let t = (t.clamp(0.0, 1.0) * HEAT_STEPS).round() / HEAT_STEPS;
+    Color::hsl(240.0 * (1.0 - t), 0.9, 0.5)
+}
+
+fn body_type_color(body_type: RigidBodyType) -> (Color, &'static str) {
+    match body_type {
+        RigidBodyType::Dynamic => (Color::srgb(0.2, 0.6, 1.0), "Dynamic"),
+        RigidBodyType::Fixed => (INACTIVE_COLOR, "Fixed"),
+        RigidBodyType::KinematicPositionBased => {
+            (Color::srgb(1.0, 0.6, 0.2), "Kinematic (position)")
+        }
+        RigidBodyType::KinematicVelocityBased => {
+            (Color::srgb(0.9, 0.3, 0.6), "Kinematic (velocity)")
+        }
+    }
+}
+
+/// Recolors the colliders with the current coloring mode, and updates its legend.
+pub fn update_state_colors(
+    mut commands: Commands,
+    context: Res<RapierContext>,
+    mut coloring: ResMut<StateColoring>,
+    mut colliders: Query<(Entity, Option<&mut ColliderStateColor>), With<ColliderRender>>,
+    mut last_mode: Local<ColorMode>,
+) {
+    let mode_changed = std::mem::replace(&mut *last_mode, coloring.mode) != coloring.mode;
+
+    if coloring.mode == ColorMode::Default {
+        if mode_changed {
+            coloring.legend.clear();
+            for (_, state_color) in colliders.iter_mut() {
+                if let Some(mut state_color) = state_color {
+                    state_color.set_if_neq(ColliderStateColor(None));
+                }
+            }
+        }
+        return;
+    }
+
+    let islands = (coloring.mode == ColorMode::Island).then(|| Islands::new(&context));
+    // The number of colliders, or bodies, of each legend entry.
+    let mut counts: HashMap<String, (Color, usize)> = HashMap::default();
+
+    for (entity, state_color) in colliders.iter_mut() {
+        let Some(co) = context
+            .entity2collider()
+            .get(&entity)
+            .and_then(|h| context.colliders.get(*h))
+        else {
+            continue;
+        };
+        let rb = co.parent().and_then(|h| Some((h, context.bodies.get(h)?)));
+
+        let (color, label) = collider_state_color(&coloring, co, rb, islands.as_ref());
+        if let Some(label) = label {
+            counts.entry(label).or_insert((color, 0)).1 += 1;
+        }
+
+        match state_color {
+            Some(mut state_color) => {
+                state_color.set_if_neq(ColliderStateColor(Some(color)));
+            }
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(ColliderStateColor(Some(color)));
+            }
+        }
+    }
+
+    coloring.legend = match coloring.mode {
+        ColorMode::Speed | ColorMode::KineticEnergy => {
+            let (max, unit) = if coloring.mode == ColorMode::Speed {
+                (coloring.max_speed, "m/s")
+            } else {
+                (coloring.max_kinetic_energy, "J")
+            };
+            let mut legend: Vec<_> = (0..=4)
+                .map(|i| {
+                    let t = i as Real / 4.0;
+                    (heat_color(t), format!("{:.2} {unit}", t * max))
+                })
+                .collect();
+            legend.last_mut().unwrap().1.insert_str(0, "≥ ");
+            legend.push((INACTIVE_COLOR, "Not dynamic".to_string()));
+            legend
+        }
+        _ => {
+            let mut legend: Vec<_> = counts
+                .into_iter()
+                .map(|(label, (color, count))| (color, count, label))
+                .collect();
+            if coloring.mode == ColorMode::Island {
+                // The largest islands first.
+                legend.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.2.cmp(&b.2)));
+                let num_islands = legend.len();
+                legend.truncate(MAX_LEGEND_ISLANDS);
+                if num_islands > MAX_LEGEND_ISLANDS {
+                    let more = format!("… {} more islands", num_islands - MAX_LEGEND_ISLANDS);
+                    legend.push((INACTIVE_COLOR, 0, more));
+                }
+            } else {
+                legend.sort_by(|a, b| a.2.cmp(&b.2));
+            }
+
+            legend
+                .into_iter()
+                .map(|(color, count, label)| match count {
+                    0 => (color, label),
+                    _ => (color, format!("{label} ({count} colliders)")),
+                })
+                .collect()
+        }
+    };
+}
+
+/// The color of a collider, and the legend entry it is counted in.
+fn collider_state_color(
+    coloring: &StateColoring,
+    co: &RapierCollider,
+    rb: Option<(RigidBodyHandle, &RigidBody)>,
+    islands: Option<&Islands>,
+) -> (Color, Option<String>) {
+    let dynamic_body = rb.filter(|(_, rb)| rb.is_dynamic());
+
+    match coloring.mode {
+        ColorMode::Default => (INACTIVE_COLOR, None),
+        ColorMode::Sleeping => match dynamic_body {
+            Some((_, rb)) if rb.is_sleeping() => {
+                (Color::srgb(0.3, 0.4, 0.9), Some("Sleeping".to_string()))
+            }
+            Some(_) => (Color::srgb(0.9, 0.5, 0.2), Some("Awake".to_string())),
+            None => (INACTIVE_COLOR, Some("Not dynamic".to_string())),
+        },
+        ColorMode::Island => {
+            let island = islands.and_then(|islands| {
+                let (handle, _) = dynamic_body?;
+                let index = *islands.indices.get(&handle)?;
+                Some((index, islands.awake.contains(&index)))
+            });
+            match island {
+                Some((index, awake)) => {
+                    let color = ColorGenerator::palette_color(index);
+                    let state = if awake { "awake" } else { "sleeping" };
+                    (color, Some(format!("Island #{}, {state}", index + 1)))
+                }
+                None => (INACTIVE_COLOR, Some("Not dynamic".to_string())),
+            }
+        }
+        ColorMode::BodyType => {
+            let body_type = rb
+                .map(|(_, rb)| rb.body_type())
+                .unwrap_or(RigidBodyType::Fixed);
+            let (color, label) = body_type_color(body_type);
+            (color, Some(label.to_string()))
+        }
+        ColorMode::Speed => match dynamic_body {
+            Some((_, rb)) => (heat_color(rb.linvel().norm() / coloring.max_speed), None),
+            None => (INACTIVE_COLOR, None),
+        },
+        ColorMode::KineticEnergy => match dynamic_body {
+            Some((_, rb)) => (
+                heat_color(rb.kinetic_energy() / coloring.max_kinetic_energy),
+                None,
+            ),
+            None => (INACTIVE_COLOR, None),
+        },
+        ColorMode::CollisionGroup => {
+            let memberships = co.collision_groups().memberships.bits();
+            if memberships == 0 {
+                (INACTIVE_COLOR, Some("No group".to_string()))
+            } else {
+                let group = memberships.trailing_zeros() as usize;
+                (
+                    ColorGenerator::palette_color(group),
+                    Some(format!("Group {}", group + 1)),
+                )
+            }
+        }
+    }
+}
+
+/// The islands of dynamic bodies touching each other, or attached by joints.
+///
+/// Like in the physics pipeline, fixed and kinematic bodies don’t connect islands.
+struct Islands {
+    /// The index of the island of each dynamic body. The islands are numbered densely,
+    /// in the order of their root body, so that they get distinct colors.
+    indices: HashMap<RigidBodyHandle, usize>,
+    /// The indices of the islands with at least one awake body.
+    awake: HashSet<usize>,
+}
+
+impl Islands {
+    fn new(context: &RapierContext) -> Self {
+        let mut parents: HashMap<RigidBodyHandle, RigidBodyHandle> = context
+            .bodies
+            .iter()
+            .filter(|(_, rb)| rb.is_dynamic())
+            .map(|(handle, _)| (handle, handle))
+            .collect();
+
+        for pair in context.narrow_phase.contact_pairs() {
+            if !pair.has_any_active_contact {
+                continue;
+            }
+            let parent = |h| context.colliders.get(h).and_then(|co| co.parent());
+            if let (Some(body1), Some(body2)) = (parent(pair.collider1), parent(pair.collider2)) {
+                union(&mut parents, body1, body2);
+            }
+        }
+
+        for (_, joint) in context.impulse_joints.iter() {
+            union(&mut parents, joint.body1, joint.body2);
+        }
+
+        for (_, _, multibody, link) in context.multibody_joints.iter() {
+            if let Some(parent) = link.parent_id().and_then(|id| multibody.link(id)) {
+                union(
+                    &mut parents,
+                    parent.rigid_body_handle(),
+                    link.rigid_body_handle(),
+                );
+            }
+        }
+
+        let handles: Vec<_> = parents.keys().copied().collect();
+        let roots: HashMap<_, _> = handles
+            .into_iter()
+            .map(|handle| (handle, find(&mut parents, handle)))
+            .collect();
+
+        let mut sorted_roots: Vec<_> = roots.values().copied().collect();
+        sorted_roots.sort_by_key(|root| root.into_raw_parts());
+        sorted_roots.dedup();
+        let root_indices: HashMap<_, _> = sorted_roots
+            .into_iter()
+            .enumerate()
+            .map(|(index, root)| (root, index))
+            .collect();
+
+        let indices: HashMap<_, _> = roots
+            .iter()
+            .map(|(handle, root)| (*handle, root_indices[root]))
+            .collect();
+        let awake = indices
+            .iter()
+            .filter(|(handle, _)| !context.bodies[**handle].is_sleeping())
+            .map(|(_, index)| *index)
+            .collect();
+
+        Self { indices, awake }
+    }
+}
+
+fn find(
+    parents: &mut HashMap<RigidBodyHandle, RigidBodyHandle>,
+    mut handle: RigidBodyHandle,
+) -> RigidBodyHandle {
+    while parents[&handle] != handle {
+        let grand_parent = parents[&parents[&handle]];
+        parents.insert(handle, grand_parent);
+        handle = grand_parent;
+    }
+    handle
+}
+
+fn union(
+    parents: &mut HashMap<RigidBodyHandle, RigidBodyHandle>,
+    body1: RigidBodyHandle,
+    body2: RigidBodyHandle,
+) {
+    if !parents.contains_key(&body1) || !parents.contains_key(&body2) {
+        return;
+    }
+
+    let (root1, root2) = (find(parents, body1), find(parents, body2));
+    // Keep the smallest handle as root, so that the islands keep their colors.
+    let (root, child) = if root1.into_raw_parts().0 < root2.into_raw_parts().0 {
+        (root1, root2)
+    } else {
+        (root2, root1)
+    };
+    parents.insert(child, root);
+}
@@ -9,6 +9,17 @@ pub mod mouse;
 mod selection_shape;
 pub mod transform_gizmo;
 
+/// The selected entities, in the order they were selected.
+#[derive(Resource, Default)]
+pub struct SelectionOrder(Vec<Entity>);
+
+impl SelectionOrder {
+    /// The rank of `entity` in the selection order, or `None` if it isn’t selected.
+    pub fn rank(&self, entity: Entity) -> Option<usize> {
+        self.0.iter().position(|e| *e == entity)
+    }
+}
+
 #[derive(Copy, Clone, Component, Default)]
 pub struct Selection {
     pub selected: bool,
@@ -43,6 +54,9 @@ pub struct SelectionState {
     pub inputs_enabled: bool,
     pub selection_start: Option<SelectableSceneObject>,
     pub selection_end: Option<SelectableSceneObject>,
+    /// The corners, in window coordinates, of the rectangle being drawn to select every
+    /// object inside of it.
+    pub selection_box: Option<(Vec2, Vec2)>,
 }
 
 struct SelectionPlugin;
@@ -51,7 +65,9 @@ impl Plugin for SelectionPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(SelectionState::default())
             .insert_resource(SceneMouse::default())
+            .insert_resource(SelectionOrder::default())
             .add_systems(Update, add_missing_selection_components)
+            .add_systems(Last, track_selection_order)
             .add_systems(
                 PostUpdate,
                 check_visibility::<With<Selection>>.in_set(VisibilitySystems::CheckVisibility),
@@ -68,6 +84,24 @@ fn add_missing_selection_components(
     }
 }
 
+/// Appends the newly selected entities to the selection order, and removes the deselected
+/// ones.
+fn track_selection_order(
+    mut order: ResMut<SelectionOrder>,
+    changed: Query<(Entity, &Selection), Changed<Selection>>,
+    mut removed: RemovedComponents<Selection>,
+) {
+    let mut deselected: Vec<_> = removed.read().collect();
+    for (entity, selection) in changed.iter() {
+        if !selection.selected() {
+            deselected.push(entity);
+        } else if order.rank(entity).is_none() {
+            order.0.push(entity);
+        }
+    }
+    order.0.retain(|entity| !deselected.contains(entity));
+}
+
 pub struct SelectionPlugins;
 
 impl PluginGroup for SelectionPlugins {
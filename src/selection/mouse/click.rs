@@ -1,7 +1,14 @@
 use crate::selection::{SceneMouse, SelectableSceneObject, Selection, SelectionState};
 use crate::ui::{ActiveMouseAction, SelectedTool, UiState};
+use crate::MainCamera;
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_rapier::prelude::*;
 
+/// The selection box is ignored if the cursor moved by less than this number of pixels.
+const MIN_BOX_SIZE: f32 = 4.0;
+
+#[allow(clippy::too_many_arguments)]
 pub fn handle_selection_click(
     mut selection_state: ResMut<SelectionState>,
     mut mouse_action: ResMut<ActiveMouseAction>,
@@ -9,15 +16,20 @@ pub fn handle_selection_click(
     mouse: Res<ButtonInput<MouseButton>>,
     keyboard: Res<ButtonInput<KeyCode>>,
     ui_state: Res<UiState>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&GlobalTransform, &Camera), With<MainCamera>>,
+    colliders: Query<(&GlobalTransform, &ViewVisibility), With<Collider>>,
     mut selected_entities: Query<(Entity, &mut Selection)>,
 ) {
-    if !selection_state.inputs_enabled {
+    if !selection_state.inputs_enabled && selection_state.selection_box.is_none() {
         return;
     }
 
     if (*mouse_action != ActiveMouseAction::Selection && *mouse_action != ActiveMouseAction::None)
         || ui_state.selected_tool == SelectedTool::Drag
         || ui_state.selected_tool == SelectedTool::Projectile
+        || ui_state.selected_tool == SelectedTool::Cut
+        || ui_state.selected_tool == SelectedTool::AddJoint
     {
         // Clear selection.
         for (_, mut selection) in selected_entities.iter_mut() {
@@ -25,16 +37,64 @@ pub fn handle_selection_click(
         }
         selection_state.selection_start = None;
         selection_state.selection_end = None;
+        selection_state.selection_box = None;
         return;
     }
 
+    let cursor = windows.get_single().ok().and_then(|w| w.cursor_position());
+    let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let box_tool = matches!(
+        ui_state.selected_tool,
+        SelectedTool::Translate | SelectedTool::Rotate
+    );
+
     if mouse.just_pressed(MouseButton::Left) {
         selection_state.selection_start = scene_mouse.hovered;
+
+        // Pressing on empty space starts a selection box.
+        if box_tool && scene_mouse.hovered.is_none() {
+            selection_state.selection_box = cursor.map(|pt| (pt, pt));
+        }
+    }
+
+    if let (Some((_, end)), Some(cursor)) = (&mut selection_state.selection_box, cursor) {
+        *end = cursor;
     }
 
     if mouse.just_released(MouseButton::Left) {
         selection_state.selection_end = scene_mouse.hovered;
 
+        if let Some((start, end)) = selection_state.selection_box.take() {
+            if start.distance(end) >= MIN_BOX_SIZE {
+                if !shift {
+                    for (_, mut selection) in selected_entities.iter_mut() {
+                        selection.selected = false;
+                    }
+                }
+
+                let rect = Rect::from_corners(start, end);
+                if let Ok((camera_transform, camera)) = camera.get_single() {
+                    for (entity, mut selection) in selected_entities.iter_mut() {
+                        let Ok((transform, visibility)) = colliders.get(entity) else {
+                            continue;
+                        };
+                        let in_box = camera
+                            .world_to_viewport(camera_transform, transform.translation())
+                            .map(|pt| rect.contains(pt))
+                            .unwrap_or(false);
+                        if visibility.get() && in_box {
+                            selection.selected = true;
+                        }
+                    }
+                }
+
+                selection_state.selection_start = None;
+                selection_state.selection_end = None;
+                *mouse_action = ActiveMouseAction::None;
+                return;
+            }
+        }
+
         if matches!(
             selection_state.selection_start,
             Some(SelectableSceneObject::SelectionShape(_))
@@ -43,7 +103,7 @@ pub fn handle_selection_click(
         }
 
         // Clear selection.
-        if !keyboard.pressed(KeyCode::ShiftLeft) {
+        if !shift {
             for (_, mut selection) in selected_entities.iter_mut() {
                 selection.selected = false;
             }
@@ -54,9 +114,9 @@ pub fn handle_selection_click(
             if let Some(hovered_object) = &scene_mouse.hovered {
                 match hovered_object {
                     SelectableSceneObject::Collider(entity, _) => {
-                        // Select object.
+                        // Select object, or toggle its selection if Shift is pressed.
                         if let Ok((_, mut selection)) = selected_entities.get_mut(*entity) {
-                            selection.selected = true;
+                            selection.selected = !(shift && selection.selected);
                             selected_any = true;
                         }
                     }
@@ -72,3 +132,27 @@ pub fn handle_selection_click(
         }
     }
 }
+
+/// Draws the rectangle of the selection box being dragged, on top of the scene.
+pub fn draw_selection_box(
+    mut ui_context: bevy_egui::EguiContexts,
+    selection_state: Res<SelectionState>,
+) {
+    use bevy_egui::egui;
+
+    if let Some((start, end)) = selection_state.selection_box {
+        let ctx = ui_context.ctx_mut();
+        let rect = egui::Rect::from_two_pos(egui::pos2(start.x, start.y), egui::pos2(end.x, end.y));
+        let visuals = ctx.style().visuals.selection;
+        ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            egui::Id::new("selection box"),
+        ))
+        .rect(
+            rect,
+            0.0,
+            visuals.bg_fill.gamma_multiply(0.25),
+            visuals.stroke,
+        );
+    }
+}
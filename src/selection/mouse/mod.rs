@@ -11,5 +11,9 @@ impl Plugin for SelectionMousePlugin {
         app.add_systems(PreUpdate, track::track_mouse_state);
         app.add_systems(Update, hover::update_hovered_entity);
         app.add_systems(Update, click::handle_selection_click);
+        app.add_systems(
+            Update,
+            click::draw_selection_box.after(click::handle_selection_click),
+        );
     }
 }
@@ -14,6 +14,7 @@ mod gizmo_material;
 mod mesh;
 mod normalization;
 
+use crate::operation::SceneHistory;
 use crate::{GizmoCamera, MainCamera, GIZMO_LAYER};
 pub use normalization::Ui3dNormalization;
 
@@ -213,12 +214,7 @@ fn drag_gizmo(
     scene_mouse: Res<SceneMouse>,
     mut gizmo_mut: Query<&mut TransformGizmo>,
     mut transform_queries: ParamSet<(
-        Query<(
-            &Selection,
-            &mut Transform,
-            &InitialTransform,
-            Option<&ReadMassProperties>,
-        )>,
+        Query<(&Selection, &mut Transform, &InitialTransform)>,
         Query<(&GlobalTransform, &Interaction), With<TransformGizmo>>,
     )>,
 ) {
@@ -269,8 +265,8 @@ fn drag_gizmo(
                     transform_queries
                         .p0()
                         .iter_mut()
-                        .filter(|(s, _t, _i, _mprops)| s.selected())
-                        .for_each(|(_s, mut t, i, _mprops)| {
+                        .filter(|(s, _t, _i)| s.selected())
+                        .for_each(|(_s, mut t, i)| {
                             let i = i.transform.compute_transform();
                             *t = Transform {
                                 translation: i.translation + translation,
@@ -292,15 +288,18 @@ fn drag_gizmo(
                         .xy()
                         .angle_between(point - gizmo_origin.xy());
 
+                    // Rotate all the selected objects around their common center.
+                    let rotation = Quat::from_rotation_z(delta_angle);
                     transform_queries
                         .p0()
                         .iter_mut()
-                        .filter(|(s, _t, _i, _mprops)| s.selected())
-                        .for_each(|(_s, mut t, i, _mprops)| {
+                        .filter(|(s, _t, _i)| s.selected())
+                        .for_each(|(_s, mut t, i)| {
                             let i = i.transform.compute_transform();
+                            let pivot = gizmo_origin.xy().extend(i.translation.z);
                             *t = Transform {
-                                translation: i.translation,
-                                rotation: Quat::from_rotation_z(delta_angle) * i.rotation,
+                                translation: pivot + rotation * (i.translation - pivot),
+                                rotation: rotation * i.rotation,
                                 scale: i.scale,
                             }
                         });
@@ -381,8 +380,8 @@ fn drag_gizmo(
                     transform_queries
                         .p0()
                         .iter_mut()
-                        .filter(|(s, _t, _i, _mprops)| s.selected())
-                        .for_each(|(_s, mut t, i, _mprops)| {
+                        .filter(|(s, _t, _i)| s.selected())
+                        .for_each(|(_s, mut t, i)| {
                             let i = i.transform.compute_transform();
                             *t = Transform {
                                 translation: i.translation + translation,
@@ -415,27 +414,19 @@ fn drag_gizmo(
                     let det = axis.dot(drag_start.cross(cursor_vector));
                     let angle = det.atan2(dot);
                     let rotation = Quat::from_axis_angle(axis, angle);
+                    // Rotate all the selected objects around their common center (which is
+                    // the center of mass if a single body is selected).
                     transform_queries
                         .p0()
                         .iter_mut()
-                        .filter(|(s, _t, _i, _mprops)| s.selected())
-                        .for_each(|(_s, mut t, i, mprops)| {
+                        .filter(|(s, _t, _i)| s.selected())
+                        .for_each(|(_s, mut t, i)| {
                             let i = i.transform.compute_transform();
-                            if let Some(mprops) = mprops {
-                                let world_com =
-                                    i.translation + i.rotation * mprops.get().local_center_of_mass;
-                                *t = Transform {
-                                    translation: world_com
-                                        + rotation * (-world_com + i.translation),
-                                    rotation: rotation * i.rotation,
-                                    scale: i.scale,
-                                }
-                            } else {
-                                *t = Transform {
-                                    translation: i.translation,
-                                    rotation: rotation * i.rotation,
-                                    scale: i.scale,
-                                }
+                            *t = Transform {
+                                translation: gizmo_origin
+                                    + rotation * (i.translation - gizmo_origin),
+                                rotation: rotation * i.rotation,
+                                scale: i.scale,
                             }
                         });
                 }
@@ -507,11 +498,13 @@ fn grab_gizmo(
     mut gizmo_query: Query<(&mut TransformGizmo, &mut Interaction, &GlobalTransform)>,
     selected_items_query: Query<(&Selection, &GlobalTransform, Entity)>,
     initial_transform_query: Query<Entity, With<InitialTransform>>,
+    mut history: ResMut<SceneHistory>,
 ) {
     if mouse_button_input.just_pressed(MouseButton::Left) {
         for (mut gizmo, mut interaction, _transform) in gizmo_query.iter_mut() {
             if *interaction == Interaction::Hovered {
                 *interaction = Interaction::Pressed;
+                history.checkpoint();
                 // Dragging has started, store the initial position of all selected meshes
                 for (selection, transform, entity) in selected_items_query.iter() {
                     if selection.selected() {
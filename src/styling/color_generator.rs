@@ -24,12 +24,22 @@ impl ColorGenerator {
         )
     }
 
+    /// The color of the `index`-th entry of a palette, with hues spread by the golden angle
+    /// so that consecutive entries are easy to tell apart.
+    pub fn palette_color(index: usize) -> Color {
+        const GOLDEN_ANGLE: f32 = 137.507_77;
+        let hue = (index as f32 * GOLDEN_ANGLE) % 360.0;
+        let lightness = [0.55, 0.4, 0.7][index % 3];
+        Color::hsl(hue, 0.75, lightness)
+    }
+
     pub fn outline_color(color: Color) -> Color {
+        let [h, s, l, a] = Hsla::from(color).to_f32_array();
         if cfg!(feature = "dim2") {
-            let [h, s, l, a] = Hsla::from(color).to_f32_array();
             Color::hsla(h, s, l * 1.2, a)
         } else {
-            color
+            // Darker, so the edges stand out on the shaded surfaces.
+            Color::hsla(h, s, l * 0.6, a)
         }
     }
 
@@ -0,0 +1,316 @@
+use crate::operation::{Operation, Operations};
+use crate::selection::Selection;
+use crate::utils::{ColliderBundle, RigidBodyBundle};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use bevy_rapier::prelude::*;
+use bevy_rapier::rapier::math::Isometry;
+use bevy_rapier::utils::iso_to_transform;
+use std::f32::consts::TAU;
+
+use super::UiState;
+
+/// The maximum number of objects along each axis of a pattern.
+const MAX_COUNT: usize = 100;
+/// The maximum number of copies generated at once, for all the selected objects.
+const MAX_TOTAL_COPIES: usize = 10_000;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ArrayPattern {
+    /// Copies along a line, `counts[0]` objects, each `spacing` away from the previous one.
+    Linear,
+    /// Copies on a grid, with `counts` objects along each axis, `spacing` apart.
+    Grid,
+    /// Copies around a circle of the given radius. The original object is on the circle.
+    Circular,
+    /// A pyramid with `counts[0]` objects on each side of its base. The horizontal
+    /// components of `spacing` separate objects of the same level, and the vertical one
+    /// separates the levels.
+    Pyramid,
+}
+
+/// The options of the array generator.
+pub struct ArrayOptions {
+    pub pattern: ArrayPattern,
+    pub counts: [usize; 3],
+    pub spacing: Vec3,
+    pub radius: f32,
+    /// Maximum random displacement of each copy along each axis.
+    pub jitter: f32,
+    pub seed: u64,
+}
+
+impl Default for ArrayOptions {
+    fn default() -> Self {
+        Self {
+            pattern: ArrayPattern::Linear,
+            counts: [5, 5, 5],
+            spacing: Vec3::ONE,
+            radius: 2.0,
+            jitter: 0.0,
+            seed: 0,
+        }
+    }
+}
+
+impl ArrayOptions {
+    /// The positions of the copies of an object placed at `source`, excluding `source` itself.
+    fn transforms(&self, source: &Transform) -> Vec<Transform> {
+        #[cfg(feature = "dim2")]
+        let (depth, up_axis) = (1, Vec3::Z);
+        #[cfg(feature = "dim3")]
+        let (depth, up_axis) = (self.counts[2], Vec3::Y);
+
+        let mut offsets = vec![];
+        let mut result = vec![];
+
+        match self.pattern {
+            ArrayPattern::Linear => {
+                for i in 1..self.counts[0] {
+                    offsets.push(self.spacing * i as f32);
+                }
+            }
+            ArrayPattern::Grid => {
+                for i in 0..self.counts[0] {
+                    for j in 0..self.counts[1] {
+                        for k in 0..depth {
+                            offsets.push(Vec3::new(i as f32, j as f32, k as f32) * self.spacing);
+                        }
+                    }
+                }
+                // The first one is the original object.
+                offsets.remove(0);
+            }
+            ArrayPattern::Circular => {
+                let center = source.translation - Vec3::X * self.radius;
+                for i in 1..self.counts[0] {
+                    let rotation =
+                        Quat::from_axis_angle(up_axis, TAU * i as f32 / self.counts[0] as f32);
+                    result.push(Transform {
+                        translation: center + rotation * (source.translation - center),
+                        rotation: rotation * source.rotation,
+                        scale: source.scale,
+                    });
+                }
+            }
+            ArrayPattern::Pyramid => {
+                let base = self.counts[0];
+                for level in 0..base {
+                    let width = base - level;
+                    let shift = level as f32 / 2.0;
+                    #[cfg(feature = "dim2")]
+                    let depth = 1;
+                    #[cfg(feature = "dim3")]
+                    let depth = width;
+
+                    for i in 0..width {
+                        for k in 0..depth {
+                            let offset =
+                                Vec3::new(i as f32 + shift, level as f32, k as f32 + shift);
+                            offsets.push(offset * self.spacing);
+                        }
+                    }
+                }
+                offsets.remove(0);
+            }
+        }
+
+        // In 2D, the objects stay on their plane.
+        #[cfg(feature = "dim2")]
+        let offsets = offsets
+            .into_iter()
+            .map(|offset| offset * Vec3::new(1.0, 1.0, 0.0));
+
+        result.extend(offsets.into_iter().map(|offset| Transform {
+            translation: source.translation + offset,
+            ..*source
+        }));
+
+        if self.jitter > 0.0 {
+            let mut rng = oorandom::Rand32::new(self.seed);
+            let mut rand = || (rng.rand_float() * 2.0 - 1.0) * self.jitter;
+            for transform in &mut result {
+                #[cfg(feature = "dim2")]
+                let jitter = Vec3::new(rand(), rand(), 0.0);
+                #[cfg(feature = "dim3")]
+                let jitter = Vec3::new(rand(), rand(), rand());
+                transform.translation += jitter;
+            }
+        }
+
+        result
+    }
+
+    fn num_copies(&self) -> usize {
+        #[cfg(feature = "dim2")]
+        let depth = 1;
+        #[cfg(feature = "dim3")]
+        let depth = self.counts[2];
+
+        match self.pattern {
+            ArrayPattern::Linear | ArrayPattern::Circular => self.counts[0],
+            ArrayPattern::Grid => self.counts[0] * self.counts[1] * depth,
+            #[cfg(feature = "dim2")]
+            ArrayPattern::Pyramid => (1..=self.counts[0]).sum::<usize>(),
+            #[cfg(feature = "dim3")]
+            ArrayPattern::Pyramid => (1..=self.counts[0]).map(|n| n * n).sum::<usize>(),
+        }
+        .saturating_sub(1)
+    }
+}
+
+pub(super) fn ui(
+    ui_context: &mut EguiContexts,
+    ui_state: &mut UiState,
+    operations: &mut Operations,
+    physics: &RapierContext,
+    selections: &Query<(Entity, &mut Selection)>,
+) {
+    let options = &mut ui_state.array;
+
+    egui::Window::new("▦ Array")
+        .open(&mut ui_state.array_open)
+        .resizable(false)
+        .show(ui_context.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut options.pattern, ArrayPattern::Linear, "Linear");
+                ui.radio_value(&mut options.pattern, ArrayPattern::Grid, "Grid");
+                ui.radio_value(&mut options.pattern, ArrayPattern::Circular, "Circular");
+                ui.radio_value(&mut options.pattern, ArrayPattern::Pyramid, "Pyramid");
+            });
+
+            let sources: Vec<_> = selections
+                .iter()
+                .filter(|(_, selection)| selection.selected())
+                .filter_map(|(entity, _)| array_source(physics, entity))
+                .collect();
+
+            egui::Grid::new("array options").show(ui, |ui| {
+                #[cfg(feature = "dim2")]
+                let num_axes = 2;
+                #[cfg(feature = "dim3")]
+                let num_axes = 3;
+                let num_counts = match options.pattern {
+                    ArrayPattern::Grid => num_axes,
+                    _ => 1,
+                };
+
+                ui.label(match options.pattern {
+                    ArrayPattern::Pyramid => "Base size:",
+                    _ => "Count:",
+                });
+                ui.horizontal(|ui| {
+                    for count in &mut options.counts[..num_counts] {
+                        ui.add(egui::DragValue::new(count).clamp_range(1..=MAX_COUNT));
+                    }
+                });
+                ui.end_row();
+
+                if options.pattern == ArrayPattern::Circular {
+                    ui.label("Radius:");
+                    ui.add(
+                        egui::DragValue::new(&mut options.radius)
+                            .clamp_range(0.0..=f32::MAX)
+                            .speed(0.1),
+                    );
+                } else {
+                    ui.label("Spacing:");
+                    ui.horizontal(|ui| {
+                        for value in &mut options.spacing.as_mut()[..num_axes] {
+                            ui.add(egui::DragValue::new(value).speed(0.1));
+                        }
+                        if ui
+                            .button("Fit")
+                            .on_hover_text("Place the copies side by side.")
+                            .clicked()
+                        {
+                            if let Some((_, _, _, size)) = sources.first() {
+                                options.spacing = *size;
+                            }
+                        }
+                    });
+                }
+                ui.end_row();
+
+                ui.label("Jitter:");
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::DragValue::new(&mut options.jitter)
+                            .clamp_range(0.0..=f32::MAX)
+                            .speed(0.01),
+                    );
+                    ui.label("Seed:");
+                    ui.add(egui::DragValue::new(&mut options.seed));
+                });
+                ui.end_row();
+            });
+
+            if sources.is_empty() {
+                ui.label("Select colliders, or rigid-bodies with a single collider, to copy them.");
+                return;
+            }
+
+            let num_copies = options.num_copies() * sources.len();
+            if num_copies > MAX_TOTAL_COPIES {
+                ui.label(format!(
+                    "Too many copies, at most {} can be generated at once.",
+                    MAX_TOTAL_COPIES
+                ));
+            }
+            if ui
+                .add_enabled(
+                    num_copies <= MAX_TOTAL_COPIES,
+                    egui::Button::new(format!("Generate {} copies", num_copies)),
+                )
+                .clicked()
+            {
+                for (collider, body, transform, _) in &sources {
+                    for copy in options.transforms(transform) {
+                        operations.push(Operation::AddCollider(collider.clone(), *body, copy));
+                    }
+                }
+            }
+        });
+}
+
+/// The collider, rigid-body, world-space transform, and size of a selected object that can be
+/// copied by `Operation::AddCollider`.
+///
+/// Rigid-bodies with several colliders, or with a collider that isn’t at their origin, aren’t
+/// supported. Colliders without rigid-body are copied without rigid-body too.
+fn array_source(
+    physics: &RapierContext,
+    entity: Entity,
+) -> Option<(ColliderBundle, Option<RigidBodyBundle>, Transform, Vec3)> {
+    let co_handle = physics.entity2collider().get(&entity)?;
+    let co = physics.colliders.get(*co_handle)?;
+    let aabb = co.compute_aabb();
+    #[cfg(feature = "dim2")]
+    let size = Vec2::from(aabb.extents()).extend(0.0);
+    #[cfg(feature = "dim3")]
+    let size = Vec3::from(aabb.extents());
+
+    match co.parent().and_then(|h| physics.bodies.get(h)) {
+        Some(rb) => {
+            let at_origin = co
+                .position_wrt_parent()
+                .map(|pos| *pos == Isometry::identity())
+                .unwrap_or(true);
+            if rb.colliders().len() != 1 || !at_origin {
+                return None;
+            }
+            Some((
+                ColliderBundle::from(co),
+                Some(RigidBodyBundle::from(rb)),
+                iso_to_transform(rb.position()),
+                size,
+            ))
+        }
+        None => Some((
+            ColliderBundle::from(co),
+            None,
+            iso_to_transform(co.position()),
+            size,
+        )),
+    }
+}
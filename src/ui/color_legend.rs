@@ -0,0 +1,67 @@
+use crate::render::{ColorMode, StateColoring};
+use bevy::prelude::*;
+use bevy_egui::egui::{self, Color32};
+use bevy_egui::EguiContexts;
+
+/// Shows what the colors mean while the colliders are colored by simulation state.
+///
+/// Closing the legend restores the colors of the colliders.
+pub(super) fn ui(ui_context: &mut EguiContexts, coloring: &mut StateColoring) {
+    if coloring.mode == ColorMode::Default {
+        return;
+    }
+
+    let title = ColorMode::ALL
+        .iter()
+        .find(|(mode, _)| *mode == coloring.mode)
+        .map(|(_, name)| *name)
+        .unwrap_or_default();
+    let mut open = true;
+
+    egui::Window::new(format!("🎨 {title}"))
+        .id(egui::Id::new("color legend"))
+        .open(&mut open)
+        .resizable(false)
+        .show(ui_context.ctx_mut(), |ui| {
+            let max = match coloring.mode {
+                ColorMode::Speed => Some(("Max speed:", &mut coloring.max_speed)),
+                ColorMode::KineticEnergy => Some(("Max energy:", &mut coloring.max_kinetic_energy)),
+                _ => None,
+            };
+            if let Some((label, max)) = max {
+                ui.horizontal(|ui| {
+                    ui.label(label);
+                    ui.add(
+                        egui::DragValue::new(max)
+                            .clamp_range(1.0e-3..=f32::MAX)
+                            .speed(0.1),
+                    );
+                });
+                ui.separator();
+            }
+
+            for (color, label) in &coloring.legend {
+                ui.horizontal(|ui| {
+                    let (rect, _) =
+                        ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
+                    ui.painter().rect_filled(rect, 2.0, egui_color(*color));
+                    ui.label(label);
+                });
+            }
+        });
+
+    if !open {
+        coloring.mode = ColorMode::Default;
+    }
+}
+
+fn egui_color(color: Color) -> Color32 {
+    let Srgba {
+        red, green, blue, ..
+    } = color.to_srgba();
+    Color32::from_rgb(
+        (red * 255.0) as u8,
+        (green * 255.0) as u8,
+        (blue * 255.0) as u8,
+    )
+}
@@ -0,0 +1,275 @@
+use crate::selection::Selection;
+use crate::styling::Theme;
+use crate::ui::UiState;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use bevy_rapier::prelude::*;
+use bevy_rapier::rapier::geometry::ColliderHandle;
+use std::collections::{HashMap, VecDeque};
+
+/// Maximum number of collision and contact force events kept in the log.
+const MAX_LOGGED_EVENTS: usize = 200;
+
+/// A collision or contact force event, with the pair of colliders involved.
+struct LoggedEvent {
+    step: usize,
+    pair: (Entity, Entity),
+    description: String,
+}
+
+/// The state of the contacts inspector window.
+#[derive(Resource, Default)]
+pub struct ContactInspector {
+    events: VecDeque<LoggedEvent>,
+    paused: bool,
+    /// The pair of colliders hovered in the window, highlighted in the viewport.
+    hovered: Option<(Entity, Entity)>,
+    /// The colliders whose events are logged, with the events and force threshold they had
+    /// before, restored when logging is disabled.
+    logged: HashMap<Entity, (ActiveEvents, Real)>,
+}
+
+impl ContactInspector {
+    fn log(&mut self, step: usize, pair: (Entity, Entity), description: String) {
+        if self.paused {
+            return;
+        }
+        if self.events.len() == MAX_LOGGED_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(LoggedEvent {
+            step,
+            pair,
+            description,
+        });
+    }
+}
+
+/// Adds the collision and contact force events of the last step to the log.
+pub fn record_contact_events(
+    progress: Res<crate::PhysicsProgress>,
+    mut inspector: ResMut<ContactInspector>,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut contact_force_events: EventReader<ContactForceEvent>,
+) {
+    for event in collision_events.read() {
+        let (pair, description) = match event {
+            CollisionEvent::Started(e1, e2, flags) => ((*e1, *e2), format!("Started {:?}", flags)),
+            CollisionEvent::Stopped(e1, e2, flags) => ((*e1, *e2), format!("Stopped {:?}", flags)),
+        };
+        inspector.log(progress.simulated_steps, pair, description);
+    }
+
+    for event in contact_force_events.read() {
+        inspector.log(
+            progress.simulated_steps,
+            (event.collider1, event.collider2),
+            format!("Force {:.3}", event.total_force_magnitude),
+        );
+    }
+}
+
+/// Highlights the pair of colliders hovered in the contacts inspector, with their contact
+/// points.
+pub fn highlight_hovered_contact(
+    mut gizmos: Gizmos,
+    theme: Res<Theme>,
+    inspector: Res<ContactInspector>,
+    context: Res<RapierContext>,
+) {
+    let Some((entity1, entity2)) = inspector.hovered else {
+        return;
+    };
+    let color = theme.insertion_preview_color();
+    let handle = |entity| context.entity2collider().get(&entity).copied();
+    let (Some(handle1), Some(handle2)) = (handle(entity1), handle(entity2)) else {
+        return;
+    };
+
+    for handle in [handle1, handle2] {
+        if let Some(co) = context.colliders.get(handle) {
+            let aabb = co.compute_aabb();
+            let center = Vect::from(aabb.center().coords);
+            let size = Vect::from(aabb.extents());
+            #[cfg(feature = "dim2")]
+            gizmos.rect_2d(center, 0.0, size, color);
+            #[cfg(feature = "dim3")]
+            gizmos.cuboid(Transform::from_translation(center).with_scale(size), color);
+        }
+    }
+
+    if let Some(pair) = context.narrow_phase.contact_pair(handle1, handle2) {
+        for manifold in &pair.manifolds {
+            for contact in &manifold.data.solver_contacts {
+                let point = Vect::from(contact.point.coords);
+                #[cfg(feature = "dim2")]
+                gizmos.circle_2d(point, 0.05, color);
+                #[cfg(feature = "dim3")]
+                gizmos.sphere(point, Quat::IDENTITY, 0.05, color);
+            }
+        }
+    }
+}
+
+pub(super) fn ui(
+    ui_context: &mut EguiContexts,
+    ui_state: &mut UiState,
+    inspector: &mut ContactInspector,
+    commands: &mut Commands,
+    physics: &RapierContext,
+    selections: &Query<(Entity, &mut Selection)>,
+) {
+    inspector.hovered = None;
+    inspector
+        .logged
+        .retain(|entity, _| physics.entity2collider().contains_key(entity));
+
+    egui::Window::new("🔍 Contacts")
+        .open(&mut ui_state.contacts_open)
+        .resizable(true)
+        .show(ui_context.ctx_mut(), |ui| {
+            let selected: Vec<_> = selections
+                .iter()
+                .filter(|(_, selection)| selection.selected())
+                .flat_map(|(entity, _)| selected_colliders(physics, entity))
+                .collect();
+
+            if selected.is_empty() {
+                ui.label("Select an object to see its contacts here.");
+            }
+
+            for (entity, handle) in &selected {
+                let Some(co) = physics.colliders.get(*handle) else {
+                    continue;
+                };
+
+                ui.horizontal(|ui| {
+                    ui.strong(format!("{:?}", entity));
+                    let mut events_enabled = inspector.logged.contains_key(entity);
+                    if ui.checkbox(&mut events_enabled, "Log events").changed() {
+                        if events_enabled {
+                            let events =
+                                ActiveEvents::from_bits_truncate(co.active_events().bits());
+                            let threshold = co.contact_force_event_threshold();
+                            inspector.logged.insert(*entity, (events, threshold));
+                            commands
+                                .entity(*entity)
+                                .insert(
+                                    events
+                                        | ActiveEvents::COLLISION_EVENTS
+                                        | ActiveEvents::CONTACT_FORCE_EVENTS,
+                                )
+                                .insert(ContactForceEventThreshold(0.0));
+                        } else if let Some((events, threshold)) = inspector.logged.remove(entity) {
+                            commands
+                                .entity(*entity)
+                                .insert(events)
+                                .insert(ContactForceEventThreshold(threshold));
+                        }
+                    }
+                });
+
+                egui::Grid::new(("contact pairs", *entity))
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("Other");
+                        ui.label("Points");
+                        ui.label("Depth");
+                        ui.label("Normal imp.");
+                        ui.label("Friction imp.");
+                        ui.end_row();
+
+                        for pair in physics.narrow_phase.contact_pairs_with(*handle) {
+                            let other = if pair.collider1 == *handle {
+                                pair.collider2
+                            } else {
+                                pair.collider1
+                            };
+                            let Some(other_entity) = physics.collider_entity(other) else {
+                                continue;
+                            };
+
+                            let mut num_points = 0;
+                            let mut depth: f32 = 0.0;
+                            let mut normal_impulse = 0.0;
+                            let mut friction_impulse = 0.0;
+                            for manifold in &pair.manifolds {
+                                for pt in &manifold.points {
+                                    num_points += 1;
+                                    depth = depth.max(-pt.dist);
+                                    normal_impulse += pt.data.impulse;
+                                    friction_impulse += pt.data.tangent_impulse.norm();
+                                }
+                            }
+
+                            let row = [
+                                format!("{:?}", other_entity),
+                                num_points.to_string(),
+                                format!("{:.4}", depth),
+                                format!("{:.4}", normal_impulse),
+                                format!("{:.4}", friction_impulse),
+                            ];
+                            let mut hovered = false;
+                            for text in row {
+                                hovered |= ui.label(text).hovered();
+                            }
+                            ui.end_row();
+
+                            if hovered {
+                                inspector.hovered = Some((*entity, other_entity));
+                            }
+                        }
+                    });
+                ui.separator();
+            }
+
+            ui.horizontal(|ui| {
+                ui.strong("Events");
+                ui.checkbox(&mut inspector.paused, "Pause");
+                if ui.button("Clear").clicked() {
+                    inspector.events.clear();
+                }
+            });
+
+            let mut hovered = None;
+            egui::ScrollArea::vertical()
+                .max_height(200.0)
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    for event in &inspector.events {
+                        let text = format!(
+                            "[{}] {:?} ↔ {:?}: {}",
+                            event.step, event.pair.0, event.pair.1, event.description
+                        );
+                        if ui.label(text).hovered() {
+                            hovered = Some(event.pair);
+                        }
+                    }
+                });
+            inspector.hovered = inspector.hovered.or(hovered);
+        });
+}
+
+/// The colliders attached to a selected entity: the collider on the entity itself, or every
+/// collider attached to the rigid-body on this entity.
+pub(super) fn selected_colliders(
+    physics: &RapierContext,
+    entity: Entity,
+) -> Vec<(Entity, ColliderHandle)> {
+    if let Some(rb) = physics
+        .entity2body()
+        .get(&entity)
+        .and_then(|h| physics.bodies.get(*h))
+    {
+        rb.colliders()
+            .iter()
+            .filter_map(|h| Some((physics.collider_entity(*h)?, *h)))
+            .collect()
+    } else {
+        physics
+            .entity2collider()
+            .get(&entity)
+            .map(|h| vec![(entity, *h)])
+            .unwrap_or_default()
+    }
+}
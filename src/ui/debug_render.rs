@@ -1,9 +1,29 @@
+use crate::render::{ColorMode, PhysicsOverlayMode, PhysicsOverlays, RenderMode};
 use crate::ui::UiState;
-use bevy_egui::egui::Ui;
+use bevy_egui::egui::{self, Ui};
 use bevy_rapier::rapier::pipeline::DebugRenderMode;
 use bevy_rapier::render::DebugRenderContext;
 
-pub(super) fn ui(ui: &mut Ui, ui_state: &mut UiState, debug_render: &mut DebugRenderContext) {
+pub(super) fn ui(
+    ui: &mut Ui,
+    ui_state: &mut UiState,
+    debug_render: &mut DebugRenderContext,
+    render_mode: &mut RenderMode,
+    color_mode: &mut ColorMode,
+    overlays: &mut PhysicsOverlays,
+) {
+    ui.label("Render mode:");
+    for (mode, text) in RenderMode::ALL {
+        ui.radio_value(render_mode, mode, text);
+    }
+    ui.separator();
+
+    ui.label("Color by:");
+    for (mode, text) in ColorMode::ALL {
+        ui.radio_value(color_mode, mode, text);
+    }
+    ui.separator();
+
     ui.checkbox(&mut debug_render.enabled, "Enabled");
     // ui.checkbox(&mut debug_render.always_on_top, "Always on top");
     ui.separator();
@@ -24,6 +44,42 @@ pub(super) fn ui(ui: &mut Ui, ui_state: &mut UiState, debug_render: &mut DebugRe
         ui.checkbox(&mut enabled, text);
         mode.set(bits, enabled);
     }
+    ui.separator();
+
+    let items = [
+        (PhysicsOverlayMode::LINEAR_VELOCITIES, "Linear velocities"),
+        (PhysicsOverlayMode::ANGULAR_VELOCITIES, "Angular velocities"),
+        (PhysicsOverlayMode::EXTERNAL_FORCES, "External forces"),
+        (PhysicsOverlayMode::CONTACT_FORCES, "Contact forces"),
+        (
+            PhysicsOverlayMode::MASS_PROPERTIES,
+            "Centers of mass & inertia",
+        ),
+    ];
+
+    for (bits, text) in items {
+        let mut enabled = overlays.mode.contains(bits);
+        ui.checkbox(&mut enabled, text);
+        overlays.mode.set(bits, enabled);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Velocity scale:");
+        ui.add(
+            egui::DragValue::new(&mut overlays.velocity_scale)
+                .clamp_range(0.0..=f32::MAX)
+                .speed(0.01),
+        );
+    });
+    ui.horizontal(|ui| {
+        ui.label("Force scale:");
+        ui.add(
+            egui::DragValue::new(&mut overlays.force_scale)
+                .clamp_range(0.0..=f32::MAX)
+                .speed(0.001),
+        );
+    });
+    ui.separator();
 
     ui.checkbox(&mut ui_state.interpolation, "Interpolation");
 }
@@ -0,0 +1,265 @@
+use super::right_panel::track_edit;
+use crate::operation::{Operation, Operations, SceneHistory};
+use bevy::prelude::*;
+use bevy_egui::egui;
+use bevy_rapier::prelude::*;
+
+/// The degrees of freedom of a joint, with their label, and whether they are angular.
+#[cfg(feature = "dim2")]
+const AXES: [(JointAxis, &str, bool); 3] = [
+    (JointAxis::LinX, "Lin. x", false),
+    (JointAxis::LinY, "Lin. y", false),
+    (JointAxis::AngX, "Ang.", true),
+];
+/// The degrees of freedom of a joint, with their label, and whether they are angular.
+#[cfg(feature = "dim3")]
+const AXES: [(JointAxis, &str, bool); 6] = [
+    (JointAxis::LinX, "Lin. x", false),
+    (JointAxis::LinY, "Lin. y", false),
+    (JointAxis::LinZ, "Lin. z", false),
+    (JointAxis::AngX, "Ang. x", true),
+    (JointAxis::AngY, "Ang. y", true),
+    (JointAxis::AngZ, "Ang. z", true),
+];
+
+/// Shows the impulse and multibody joints attached to the given body.
+pub(super) fn ui(
+    commands: &mut Commands,
+    ui: &mut egui::Ui,
+    history: &mut SceneHistory,
+    operations: &mut Operations,
+    entity: Entity,
+    impulse_joints: &mut Query<(Entity, &mut ImpulseJoint, Option<&Parent>)>,
+    multibody_joints: &mut Query<(Entity, &mut MultibodyJoint)>,
+) {
+    for (joint_entity, mut joint, parent) in impulse_joints.iter_mut() {
+        let body2 = parent.map(|p| p.get()).unwrap_or(joint_entity);
+        if joint.parent != entity && body2 != entity && joint_entity != entity {
+            continue;
+        }
+
+        let title = format!("Impulse joint: {}", joint_type_name(&joint.data));
+        egui::CollapsingHeader::new(title)
+            .id_source(("Impulse joint", joint_entity))
+            .show(ui, |ui| {
+                ui.label(format!("Bodies: {:?} → {:?}", joint.parent, body2));
+
+                let mut data = joint.data;
+                joint_ui(ui, history, joint_entity, &mut data);
+                if data != joint.data {
+                    joint.data = data;
+                }
+
+                if ui.button("Delete").clicked() {
+                    if parent.is_some() {
+                        // The joint has its own entity, attached to the second body.
+                        operations.push(Operation::Despawn(vec![joint_entity]));
+                    } else {
+                        history.checkpoint();
+                        commands.entity(joint_entity).remove::<ImpulseJoint>();
+                    }
+                }
+            });
+    }
+
+    for (body2, mut joint) in multibody_joints.iter_mut() {
+        if joint.parent != entity && body2 != entity {
+            continue;
+        }
+
+        let title = format!("Multibody joint: {}", joint_type_name(&joint.data));
+        egui::CollapsingHeader::new(title)
+            .id_source(("Multibody joint", body2))
+            .show(ui, |ui| {
+                ui.label(format!("Bodies: {:?} → {:?}", joint.parent, body2));
+
+                let mut data = joint.data;
+                joint_ui(ui, history, body2, &mut data);
+                if data != joint.data {
+                    joint.data = data;
+                }
+
+                if ui.button("Delete").clicked() {
+                    history.checkpoint();
+                    commands.entity(body2).remove::<MultibodyJoint>();
+                }
+            });
+    }
+}
+
+fn joint_type_name(joint: &TypedJoint) -> &'static str {
+    match joint {
+        TypedJoint::FixedJoint(_) => "Fixed",
+        TypedJoint::GenericJoint(_) => "Generic",
+        TypedJoint::PrismaticJoint(_) => "Prismatic",
+        TypedJoint::RevoluteJoint(_) => "Revolute",
+        TypedJoint::RopeJoint(_) => "Rope",
+        #[cfg(feature = "dim3")]
+        TypedJoint::SphericalJoint(_) => "Spherical",
+        TypedJoint::SpringJoint(_) => "Spring",
+    }
+}
+
+/// Edits the anchors, and the limits and motors of the free axes of a joint.
+fn joint_ui(ui: &mut egui::Ui, history: &mut SceneHistory, id: Entity, joint: &mut TypedJoint) {
+    let data: &mut GenericJoint = joint.as_mut();
+
+    egui::Grid::new(("Joint props", id)).show(ui, |ui| {
+        let mut anchor1 = data.local_anchor1();
+        ui.label("Anchor 1: ");
+        if vector_ui(ui, history, &mut anchor1) {
+            data.set_local_anchor1(anchor1);
+        }
+        ui.end_row();
+
+        let mut anchor2 = data.local_anchor2();
+        ui.label("Anchor 2: ");
+        if vector_ui(ui, history, &mut anchor2) {
+            data.set_local_anchor2(anchor2);
+        }
+        ui.end_row();
+
+        let mut contacts_enabled = data.contacts_enabled();
+        ui.label("Contacts: ");
+        if track_edit(history, ui.checkbox(&mut contacts_enabled, "")) {
+            data.set_contacts_enabled(contacts_enabled);
+        }
+        ui.end_row();
+    });
+
+    let locked_axes = data.locked_axes();
+
+    for (axis, label, angular) in AXES {
+        if locked_axes.contains(axis.into()) {
+            continue;
+        }
+
+        ui.separator();
+        ui.label(label);
+
+        egui::Grid::new(("Joint axis props", id, axis as usize)).show(ui, |ui| {
+            let mut has_limits = data.limits(axis).is_some();
+            ui.label("Limits: ");
+            if track_edit(history, ui.checkbox(&mut has_limits, "")) {
+                if has_limits {
+                    let max = if angular {
+                        std::f32::consts::FRAC_PI_2
+                    } else {
+                        1.0
+                    };
+                    data.set_limits(axis, [-max, max]);
+                } else {
+                    data.raw.limit_axes.remove(axis.into());
+                }
+            }
+
+            if let Some(limits) = data.limits(axis).copied() {
+                let mut bounds = [limits.min, limits.max];
+                let changed = if angular {
+                    track_edit(history, ui.drag_angle(&mut bounds[0]))
+                        | track_edit(history, ui.drag_angle(&mut bounds[1]))
+                } else {
+                    track_edit(
+                        history,
+                        ui.add(egui::DragValue::new(&mut bounds[0]).speed(0.01)),
+                    ) | track_edit(
+                        history,
+                        ui.add(egui::DragValue::new(&mut bounds[1]).speed(0.01)),
+                    )
+                };
+                if changed {
+                    bounds[1] = bounds[1].max(bounds[0]);
+                    data.set_limits(axis, bounds);
+                }
+            }
+            ui.end_row();
+
+            let mut has_motor = data.motor(axis).is_some();
+            ui.label("Motor: ");
+            if track_edit(history, ui.checkbox(&mut has_motor, "")) {
+                if has_motor {
+                    data.set_motor(axis, 0.0, 0.0, 0.0, 0.0);
+                } else {
+                    data.raw.motor_axes.remove(axis.into());
+                }
+            }
+            ui.end_row();
+
+            if let Some(motor) = data.motor(axis).copied() {
+                let mut new_motor = motor;
+
+                ui.label("Target pos.: ");
+                if angular {
+                    track_edit(history, ui.drag_angle(&mut new_motor.target_pos));
+                } else {
+                    track_edit(
+                        history,
+                        ui.add(egui::DragValue::new(&mut new_motor.target_pos).speed(0.01)),
+                    );
+                }
+                ui.end_row();
+
+                ui.label("Target vel.: ");
+                track_edit(
+                    history,
+                    ui.add(egui::DragValue::new(&mut new_motor.target_vel).speed(0.01)),
+                );
+                ui.end_row();
+
+                ui.label("Stiffness: ");
+                track_edit(
+                    history,
+                    ui.add(
+                        egui::DragValue::new(&mut new_motor.stiffness)
+                            .clamp_range(0.0..=f32::MAX)
+                            .speed(0.1),
+                    ),
+                );
+                ui.end_row();
+
+                ui.label("Damping: ");
+                track_edit(
+                    history,
+                    ui.add(
+                        egui::DragValue::new(&mut new_motor.damping)
+                            .clamp_range(0.0..=f32::MAX)
+                            .speed(0.1),
+                    ),
+                );
+                ui.end_row();
+
+                ui.label("Max force: ");
+                track_edit(
+                    history,
+                    ui.add(
+                        egui::DragValue::new(&mut new_motor.max_force)
+                            .clamp_range(0.0..=f32::MAX)
+                            .speed(0.1),
+                    ),
+                );
+                ui.end_row();
+
+                if new_motor != motor {
+                    data.set_motor(
+                        axis,
+                        new_motor.target_pos,
+                        new_motor.target_vel,
+                        new_motor.stiffness,
+                        new_motor.damping,
+                    )
+                    .set_motor_max_force(axis, new_motor.max_force);
+                }
+            }
+        });
+    }
+}
+
+fn vector_ui(ui: &mut egui::Ui, history: &mut SceneHistory, v: &mut Vect) -> bool {
+    let mut changed = track_edit(history, ui.add(egui::DragValue::new(&mut v.x).speed(0.01)));
+    changed |= track_edit(history, ui.add(egui::DragValue::new(&mut v.y).speed(0.01)));
+    #[cfg(feature = "dim3")]
+    {
+        changed |= track_edit(history, ui.add(egui::DragValue::new(&mut v.z).speed(0.01)));
+    }
+    changed
+}
@@ -1,16 +1,121 @@
-use crate::selection::Selection;
+use crate::control::CharacterControlOptions;
+use crate::operation::{Clipboard, CopiedObjects, Operation, Operations, SceneHistory};
+use crate::render::ColliderRender;
+use crate::selection::{SceneMouse, Selection};
 use bevy::prelude::*;
+use bevy_egui::EguiContexts;
+use bevy_rapier::control::KinematicCharacterController;
+use bevy_rapier::plugin::RapierContext;
 
 pub fn handle_keyboard_inputs(
-    mut commands: Commands,
+    mut ui_context: EguiContexts,
     keys: Res<ButtonInput<KeyCode>>,
-    selection: Query<(Entity, &Selection)>,
+    mut history: ResMut<SceneHistory>,
+    mut operations: ResMut<Operations>,
+    mut selection: Query<(Entity, &mut Selection, Option<&Visibility>)>,
 ) {
-    for (entity, selection) in selection.iter() {
-        if selection.selected() {
-            if keys.just_released(KeyCode::Delete) {
-                commands.entity(entity).despawn_recursive();
+    // The shortcuts are left to the text fields being edited.
+    if ui_context.ctx_mut().wants_keyboard_input() {
+        return;
+    }
+
+    let ctrl = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+
+    if ctrl && keys.just_pressed(KeyCode::KeyZ) {
+        if shift {
+            history.redo();
+        } else {
+            history.undo();
+        }
+    }
+
+    if ctrl {
+        let select_all = keys.just_pressed(KeyCode::KeyA);
+        let invert = keys.just_pressed(KeyCode::KeyI);
+
+        if select_all || invert {
+            for (_, mut selection, visibility) in selection.iter_mut() {
+                if visibility != Some(&Visibility::Hidden) {
+                    selection.selected = select_all || !selection.selected;
+                }
             }
         }
     }
+
+    if keys.just_released(KeyCode::Delete) {
+        let selected: Vec<_> = selection
+            .iter()
+            .filter(|(_, s, _)| s.selected())
+            .map(|(entity, ..)| entity)
+            .collect();
+        if !selected.is_empty() {
+            operations.push(Operation::Despawn(selected));
+        }
+    }
+}
+
+/// Copies the selected objects with Ctrl+C, pastes them at the cursor with Ctrl+V, and
+/// duplicates them with Ctrl+D (without replacing the copied objects).
+#[allow(clippy::too_many_arguments)]
+pub fn handle_clipboard_inputs(
+    mut ui_context: EguiContexts,
+    keys: Res<ButtonInput<KeyCode>>,
+    context: Res<RapierContext>,
+    scene_mouse: Res<SceneMouse>,
+    mut clipboard: ResMut<Clipboard>,
+    mut operations: ResMut<Operations>,
+    renders: Query<(Entity, &ColliderRender)>,
+    names: Query<&Name>,
+    character_controllers: Query<(&KinematicCharacterController, &CharacterControlOptions)>,
+    selection: Query<(Entity, &Selection)>,
+) {
+    let ctrl = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    if !ctrl || ui_context.ctx_mut().wants_keyboard_input() {
+        return;
+    }
+
+    let copy = || {
+        CopiedObjects::new(
+            &context,
+            selection
+                .iter()
+                .filter(|(_, s)| s.selected())
+                .map(|(entity, _)| entity),
+            &renders,
+            &names,
+            &character_controllers,
+        )
+    };
+
+    #[cfg(feature = "dim2")]
+    let cursor = scene_mouse.point;
+    #[cfg(feature = "dim3")]
+    let cursor = scene_mouse.ray.and_then(|(ray_pos, ray_dir)| {
+        // Cast against the ground.
+        let ground_hit = ray_pos.y / -ray_dir.y;
+        (ray_dir.y.abs() > 1.0e-3 && ground_hit > 0.0).then(|| ray_pos + ray_dir * ground_hit)
+    });
+
+    if keys.just_pressed(KeyCode::KeyC) {
+        let objects = copy();
+        if !objects.is_empty() {
+            clipboard.objects = Some(objects);
+        }
+    }
+
+    let pasted = if keys.just_pressed(KeyCode::KeyV) {
+        clipboard.objects.clone()
+    } else if keys.just_pressed(KeyCode::KeyD) {
+        Some(copy())
+    } else {
+        None
+    };
+
+    if let Some(objects) = pasted.filter(|objects| !objects.is_empty()) {
+        operations.push(Operation::PasteObjects {
+            objects,
+            position: cursor,
+        });
+    }
 }
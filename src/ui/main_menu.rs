@@ -1,5 +1,6 @@
 use crate::builtin_scenes;
 use crate::operation::{Operation, Operations};
+use crate::render::{ColorMode, PhysicsOverlays, RenderMode};
 use crate::styling::Theme;
 use crate::ui::{debug_render, UiState};
 use bevy::app::AppExit;
@@ -14,6 +15,7 @@ use crate::builtin_scenes::BuiltinScene;
 #[cfg(not(target_arch = "wasm32"))]
 use native_dialog::FileDialog;
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn ui(
     _window: &Window,
     theme: &mut Theme,
@@ -22,6 +24,9 @@ pub(super) fn ui(
     _physics_context: &mut RapierContext,
     _physics_config: &mut RapierConfiguration,
     debug_render_context: &mut DebugRenderContext,
+    render_mode: &mut RenderMode,
+    color_mode: &mut ColorMode,
+    overlays: &mut PhysicsOverlays,
     operations: &mut Operations,
     mut exit: EventWriter<AppExit>,
 ) {
@@ -62,7 +67,14 @@ pub(super) fn ui(
                     }
 
                     ui.menu_button("🐞 Debug render", |ui| {
-                        debug_render::ui(ui, ui_state, &mut *debug_render_context);
+                        debug_render::ui(
+                            ui,
+                            ui_state,
+                            &mut *debug_render_context,
+                            render_mode,
+                            color_mode,
+                            overlays,
+                        );
                     });
 
                     ui.checkbox(&mut theme.dark_mode, "Dark mode");
@@ -71,6 +83,18 @@ pub(super) fn ui(
                         ui_state.simulation_infos_open = true;
                         ui.close_menu();
                     }
+                    if ui.button("⏱ Profiler…").clicked() {
+                        ui_state.profiler_open = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("🔍 Contacts…").clicked() {
+                        ui_state.contacts_open = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("▦ Array…").clicked() {
+                        ui_state.array_open = true;
+                        ui.close_menu();
+                    }
                     ui.separator();
                     if ui.button("❌ Clear scene").clicked() {
                         operations.push(Operation::ClearScene)
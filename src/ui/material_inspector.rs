@@ -0,0 +1,318 @@
+use super::right_panel::track_edit;
+use crate::operation::SceneHistory;
+use crate::utils::{ColliderComponentsMut, PhysicsMaterial};
+use bevy::prelude::*;
+use bevy_egui::egui;
+use bevy_rapier::prelude::*;
+use std::path::PathBuf;
+
+const COMBINE_RULES: [(CoefficientCombineRule, &str); 4] = [
+    (CoefficientCombineRule::Average, "Average"),
+    (CoefficientCombineRule::Min, "Min"),
+    (CoefficientCombineRule::Multiply, "Multiply"),
+    (CoefficientCombineRule::Max, "Max"),
+];
+
+/// The named physics materials that can be applied to the selected colliders.
+pub struct MaterialPresets {
+    presets: Vec<(String, PhysicsMaterial)>,
+    /// The name given to the next saved preset.
+    new_name: String,
+}
+
+impl Default for MaterialPresets {
+    /// The presets saved by the user, or the built-in presets if there are none.
+    fn default() -> Self {
+        let presets = match load_presets() {
+            Ok(Some(presets)) => presets,
+            Ok(None) => builtin_presets(),
+            Err(e) => {
+                error!("Failed to load the material presets: {:?}", e);
+                builtin_presets()
+            }
+        };
+
+        Self {
+            presets,
+            new_name: String::new(),
+        }
+    }
+}
+
+fn builtin_presets() -> Vec<(String, PhysicsMaterial)> {
+    let rubber = PhysicsMaterial {
+        friction_combine_rule: CoefficientCombineRule::Max,
+        restitution_combine_rule: CoefficientCombineRule::Max,
+        ..PhysicsMaterial::new(1.0, 0.8)
+    };
+    let ice = PhysicsMaterial {
+        friction_combine_rule: CoefficientCombineRule::Min,
+        ..PhysicsMaterial::new(0.02, 0.1)
+    };
+
+    vec![
+        ("Ice".to_string(), ice),
+        ("Rubber".to_string(), rubber),
+        ("Steel".to_string(), PhysicsMaterial::new(0.6, 0.5)),
+        ("Wood".to_string(), PhysicsMaterial::new(0.4, 0.3)),
+    ]
+}
+
+/// The file where the presets are saved, in the configuration directory of the user.
+fn presets_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .or_else(|| std::env::var_os("APPDATA"))
+        .map(PathBuf::from)
+        .or_else(|| Some(PathBuf::from(std::env::var_os("HOME")?).join(".config")))?;
+    Some(config_dir.join("steadyum").join("material_presets.json"))
+}
+
+fn load_presets() -> anyhow::Result<Option<Vec<(String, PhysicsMaterial)>>> {
+    let Some(path) = presets_path().filter(|path| path.exists()) else {
+        return Ok(None);
+    };
+    let data = std::fs::read(path)?;
+    Ok(Some(serde_json::from_slice(&data)?))
+}
+
+fn store_presets(presets: &[(String, PhysicsMaterial)]) -> anyhow::Result<()> {
+    let Some(path) = presets_path() else {
+        anyhow::bail!("no configuration directory");
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_vec_pretty(presets)?)?;
+    Ok(())
+}
+
+impl MaterialPresets {
+    /// Adds a preset, or replaces the preset with the same name, and writes the presets to
+    /// the configuration file so they are kept after a restart.
+    fn save(&mut self, name: String, material: PhysicsMaterial) {
+        match self.presets.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, preset)) => *preset = material,
+            None => self.presets.push((name, material)),
+        }
+
+        if let Err(e) = store_presets(&self.presets) {
+            error!("Failed to save the material presets: {:?}", e);
+        }
+    }
+
+    /// A menu listing the presets, returning the one clicked.
+    pub(super) fn menu(&self, ui: &mut egui::Ui, label: &str) -> Option<PhysicsMaterial> {
+        let mut clicked = None;
+        ui.menu_button(label, |ui| {
+            for (name, material) in &self.presets {
+                if ui.button(name).clicked() {
+                    clicked = Some(*material);
+                    ui.close_menu();
+                }
+            }
+        });
+        clicked
+    }
+}
+
+/// Edits the friction, restitution, and contact skin of the given colliders.
+///
+/// The material of the first collider is shown. Only the fields modified by the user are
+/// applied to all of them, so the other fields keep their own values, while a preset replaces
+/// their whole material.
+pub(super) fn ui(
+    commands: &mut Commands,
+    ui: &mut egui::Ui,
+    history: &mut SceneHistory,
+    presets: &mut MaterialPresets,
+    targets: &[Entity],
+    colliders: &mut Query<ColliderComponentsMut>,
+) {
+    let Some(Ok((.., friction, restitution, contact_skin))) =
+        targets.first().map(|entity| colliders.get(*entity))
+    else {
+        return;
+    };
+    let old_material = PhysicsMaterial::from_components(friction, restitution, contact_skin);
+    let mut material = old_material;
+    let mut preset_applied = false;
+
+    material_ui(ui, &mut material, |response| {
+        track_edit(history, response);
+    });
+
+    ui.horizontal(|ui| {
+        if let Some(preset) = presets.menu(ui, "Apply preset") {
+            history.checkpoint();
+            material = preset;
+            preset_applied = true;
+        }
+
+        ui.add(
+            egui::TextEdit::singleline(&mut presets.new_name)
+                .hint_text("Preset name")
+                .desired_width(80.0),
+        );
+        if ui
+            .add_enabled(!presets.new_name.is_empty(), egui::Button::new("Save"))
+            .clicked()
+        {
+            let name = std::mem::take(&mut presets.new_name);
+            presets.save(name, material);
+        }
+    });
+
+    if preset_applied {
+        for entity in targets {
+            apply(commands, colliders, *entity, &material);
+        }
+    } else if material != old_material {
+        for entity in targets {
+            let Ok((.., friction, restitution, contact_skin)) = colliders.get(*entity) else {
+                continue;
+            };
+            let target_material = with_changes(
+                PhysicsMaterial::from_components(friction, restitution, contact_skin),
+                &old_material,
+                &material,
+            );
+            apply(commands, colliders, *entity, &target_material);
+        }
+    }
+}
+
+/// The fields of `material` modified from `old` to `new` set to their new values.
+fn with_changes(
+    mut material: PhysicsMaterial,
+    old: &PhysicsMaterial,
+    new: &PhysicsMaterial,
+) -> PhysicsMaterial {
+    if new.friction != old.friction {
+        material.friction = new.friction;
+    }
+    if new.friction_combine_rule != old.friction_combine_rule {
+        material.friction_combine_rule = new.friction_combine_rule;
+    }
+    if new.restitution != old.restitution {
+        material.restitution = new.restitution;
+    }
+    if new.restitution_combine_rule != old.restitution_combine_rule {
+        material.restitution_combine_rule = new.restitution_combine_rule;
+    }
+    if new.contact_skin != old.contact_skin {
+        material.contact_skin = new.contact_skin;
+    }
+    material
+}
+
+/// Sets the material of a collider, inserting the components it doesn’t have yet.
+pub(super) fn apply(
+    commands: &mut Commands,
+    colliders: &mut Query<ColliderComponentsMut>,
+    entity: Entity,
+    material: &PhysicsMaterial,
+) {
+    let Ok((.., friction, restitution, contact_skin)) = colliders.get_mut(entity) else {
+        return;
+    };
+
+    match friction {
+        Some(mut friction) => *friction = material.friction(),
+        None => {
+            commands.entity(entity).insert(material.friction());
+        }
+    }
+    match restitution {
+        Some(mut restitution) => *restitution = material.restitution(),
+        None => {
+            commands.entity(entity).insert(material.restitution());
+        }
+    }
+    match contact_skin {
+        Some(mut contact_skin) => contact_skin.0 = material.contact_skin,
+        None => {
+            commands
+                .entity(entity)
+                .insert(ContactSkin(material.contact_skin));
+        }
+    }
+}
+
+fn combine_rule_ui(
+    ui: &mut egui::Ui,
+    id: &str,
+    rule: &mut CoefficientCombineRule,
+    on_edit: &mut impl FnMut(egui::Response),
+) {
+    egui::ComboBox::from_id_source(id)
+        .selected_text(format!("{:?}", rule))
+        .show_ui(ui, |ui| {
+            for (value, label) in COMBINE_RULES {
+                on_edit(ui.selectable_value(rule, value, label));
+            }
+        });
+}
+
+/// Edits the material of the colliders created with the insertion tools.
+pub(super) fn new_body_ui(
+    ui: &mut egui::Ui,
+    presets: &MaterialPresets,
+    material: &mut PhysicsMaterial,
+) {
+    ui.label("Material of the new colliders:");
+    material_ui(ui, material, |_| {});
+    if let Some(preset) = presets.menu(ui, "Apply preset") {
+        *material = preset;
+    }
+}
+
+/// Edits a material, passing the response of each widget to `on_edit`.
+fn material_ui(
+    ui: &mut egui::Ui,
+    material: &mut PhysicsMaterial,
+    mut on_edit: impl FnMut(egui::Response),
+) {
+    egui::Grid::new("Collider material").show(ui, |ui| {
+        ui.label("Friction: ");
+        on_edit(
+            ui.add(
+                egui::DragValue::new(&mut material.friction)
+                    .clamp_range(0.0..=f32::MAX)
+                    .speed(0.01),
+            ),
+        );
+        combine_rule_ui(
+            ui,
+            "Friction combine rule",
+            &mut material.friction_combine_rule,
+            &mut on_edit,
+        );
+        ui.end_row();
+
+        ui.label("Restitution: ");
+        on_edit(
+            ui.add(
+                egui::DragValue::new(&mut material.restitution)
+                    .clamp_range(0.0..=1.0)
+                    .speed(0.01),
+            ),
+        );
+        combine_rule_ui(
+            ui,
+            "Restitution combine rule",
+            &mut material.restitution_combine_rule,
+            &mut on_edit,
+        );
+        ui.end_row();
+
+        ui.label("Contact skin: ");
+        on_edit(
+            ui.add(
+                egui::DragValue::new(&mut material.contact_skin)
+                    .clamp_range(0.0..=f32::MAX)
+                    .speed(0.001),
+            ),
+        );
+        ui.end_row();
+    });
+}
@@ -0,0 +1,118 @@
+use crate::operation::{Operation, Operations};
+use bevy_egui::{egui, EguiContexts};
+use bevy_rapier::geometry::{ComputedColliderShape, VHACDParameters};
+use std::path::PathBuf;
+
+use super::UiState;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MeshColliderKind {
+    TriMesh,
+    ConvexHull,
+    ConvexDecomposition,
+}
+
+/// The options chosen for a mesh being imported.
+pub struct MeshImportOptions {
+    pub path: PathBuf,
+    pub kind: MeshColliderKind,
+    pub vhacd: VHACDParameters,
+}
+
+impl MeshImportOptions {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            kind: MeshColliderKind::TriMesh,
+            vhacd: VHACDParameters::default(),
+        }
+    }
+
+    pub fn shape(&self) -> ComputedColliderShape {
+        match self.kind {
+            MeshColliderKind::TriMesh => ComputedColliderShape::TriMesh,
+            MeshColliderKind::ConvexHull => ComputedColliderShape::ConvexHull,
+            MeshColliderKind::ConvexDecomposition => {
+                ComputedColliderShape::ConvexDecomposition(self.vhacd.clone())
+            }
+        }
+    }
+}
+
+pub(super) fn ui(
+    ui_context: &mut EguiContexts,
+    ui_state: &mut UiState,
+    operations: &mut Operations,
+) {
+    let Some(options) = &mut ui_state.mesh_import else {
+        return;
+    };
+
+    let mut close = false;
+    let title = options
+        .path
+        .file_name()
+        .map(|name| format!("Import {}", name.to_string_lossy()))
+        .unwrap_or_else(|| "Import mesh".to_string());
+
+    egui::Window::new(title)
+        .resizable(false)
+        .collapsible(false)
+        .show(ui_context.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut options.kind, MeshColliderKind::TriMesh, "Trimesh");
+                ui.radio_value(
+                    &mut options.kind,
+                    MeshColliderKind::ConvexHull,
+                    "Convex hull",
+                );
+                ui.radio_value(
+                    &mut options.kind,
+                    MeshColliderKind::ConvexDecomposition,
+                    "Convex decomposition",
+                );
+            });
+
+            if options.kind == MeshColliderKind::TriMesh {
+                ui.label("Imported as a fixed body.");
+            }
+
+            if options.kind == MeshColliderKind::ConvexDecomposition {
+                ui.add(
+                    egui::Slider::new(&mut options.vhacd.resolution, 16..=512).text("Resolution"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut options.vhacd.concavity, 0.0..=1.0)
+                        .logarithmic(true)
+                        .text("Concavity"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut options.vhacd.max_convex_hulls, 1..=1024)
+                        .logarithmic(true)
+                        .text("Max hulls"),
+                );
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Preview").clicked() {
+                    operations.push(Operation::PreviewMesh(
+                        options.path.clone(),
+                        options.shape(),
+                    ));
+                }
+                if ui.button("Import").clicked() {
+                    operations.push(Operation::ImportMesh(options.path.clone(), options.shape()));
+                    close = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    operations.push(Operation::ClearMeshPreview);
+                    close = true;
+                }
+            });
+        });
+
+    if close {
+        ui_state.mesh_import = None;
+    }
+}
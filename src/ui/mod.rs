@@ -8,6 +8,7 @@ use bevy_egui::{
     EguiContexts,
 };
 use bevy_rapier::control::KinematicCharacterController;
+use bevy_rapier::dynamics::{ImpulseJoint, MultibodyJoint};
 use bevy_rapier::plugin::{RapierConfiguration, RapierContext};
 use bevy_rapier::render::DebugRenderContext;
 use strum_macros::EnumIter;
@@ -16,23 +17,41 @@ use ui_state::OpenObjectTab;
 pub use self::plugin::RapierUiPlugin;
 use crate::cli::CliArgs;
 use crate::control::CharacterControlOptions;
-use crate::operation::Operations;
+use crate::operation::{Operations, SceneHistory, SimulationTimeline};
+use crate::render::{PhysicsOverlays, RenderMode, StateColoring};
 use crate::styling::Theme;
+use crate::PhysicsProgress;
+pub(self) use contacts_inspector::{
+    highlight_hovered_contact, record_contact_events, ContactInspector,
+};
 pub(self) use gizmo::add_missing_gizmos;
 pub(self) use input_blocking::focus_ui;
-pub(self) use keyboard::handle_keyboard_inputs;
+pub(self) use keyboard::{handle_clipboard_inputs, handle_keyboard_inputs};
+pub(self) use profiler::{record_profile, ProfilerHistory};
+#[cfg(feature = "dim2")]
+pub use ui_state::DrawShapeMode;
 pub use ui_state::{ActiveMouseAction, SelectedTool, UiState};
 
+mod array_generator;
+mod color_legend;
+mod contacts_inspector;
 mod debug_render;
 mod gizmo;
 mod input_blocking;
+mod joint_inspector;
 mod keyboard;
 mod main_menu;
+mod material_inspector;
+#[cfg(feature = "dim3")]
+mod mesh_import;
 mod play_stop;
 mod plugin;
 mod popup_menu;
+mod profiler;
 mod right_panel;
+mod scene_explorer;
 mod simulation_infos;
+mod timeline;
 mod tools;
 mod ui_state;
 
@@ -48,6 +67,7 @@ pub enum ButtonTexture {
     Rotate,
     Drag,
     Projectile,
+    AddJoint,
     AddBall,
     AddCuboid,
     AddPlane,
@@ -78,6 +98,7 @@ impl ButtonTexture {
             Self::Rotate => "",
             Self::Drag => "",
             Self::Projectile => "",
+            Self::AddJoint => "",
             #[cfg(feature = "dim2")]
             Self::AddBall => "",
             #[cfg(feature = "dim2")]
@@ -118,7 +139,12 @@ impl ButtonTexture {
             Self::Undo | Self::Redo => txt
                 .color(Color32::LIGHT_BLUE)
                 .font(egui::FontId::monospace(40.0).clone()),
-            Self::Cut | Self::Translate | Self::Rotate | Self::Drag | Self::Projectile => txt
+            Self::Cut
+            | Self::Translate
+            | Self::Rotate
+            | Self::Drag
+            | Self::Projectile
+            | Self::AddJoint => txt
                 .color(Color32::LIGHT_BLUE)
                 .font(egui::FontId::monospace(20.0).clone()),
             Self::AddBall
@@ -160,15 +186,29 @@ pub fn load_assets(
 
 pub fn update_ui(
     mut commands: Commands,
-    (cli, mut theme): (Res<CliArgs>, ResMut<Theme>),
+    (cli, mut theme, progress, mut profiler, mut contacts): (
+        Res<CliArgs>,
+        ResMut<Theme>,
+        Res<PhysicsProgress>,
+        ResMut<ProfilerHistory>,
+        ResMut<ContactInspector>,
+    ),
     mut ui_context: EguiContexts,
     mut ui_state: ResMut<UiState>,
-    mut debug_render_context: ResMut<DebugRenderContext>,
+    (mut debug_render_context, mut render_mode, mut coloring, mut overlays): (
+        ResMut<DebugRenderContext>,
+        ResMut<RenderMode>,
+        ResMut<StateColoring>,
+        ResMut<PhysicsOverlays>,
+    ),
     mut physics_context: ResMut<RapierContext>,
     mut physics_config: ResMut<RapierConfiguration>,
-    mut operations: ResMut<Operations>,
-    exit: EventWriter<AppExit>,
-    windows: Query<&Window, With<PrimaryWindow>>,
+    (mut operations, mut history, mut timeline): (
+        ResMut<Operations>,
+        ResMut<SceneHistory>,
+        ResMut<SimulationTimeline>,
+    ),
+    (exit, windows): (EventWriter<AppExit>, Query<&Window, With<PrimaryWindow>>),
     mut bodies: Query<RigidBodyComponentsMut>,
     mut colliders: Query<ColliderComponentsMut>,
     mut character_controllers: Query<(
@@ -177,9 +217,17 @@ pub fn update_ui(
     )>,
     mut selections: Query<(Entity, &mut Selection)>,
     mut visibility: Query<(Entity, &mut Visibility)>,
-    mut transforms: Query<(Entity, &mut Transform)>,
+    (mut transforms, names, mut impulse_joints, mut multibody_joints): (
+        Query<(Entity, &mut Transform)>,
+        Query<&Name>,
+        Query<(Entity, &mut ImpulseJoint, Option<&Parent>)>,
+        Query<(Entity, &mut MultibodyJoint)>,
+    ),
 ) {
     if let Ok(window) = windows.get_single() {
+        // Only mark the render mode as changed when it is actually modified, since changing it
+        // re-renders all the colliders.
+        let mut new_render_mode = *render_mode;
         main_menu::ui(
             window,
             &mut theme,
@@ -188,9 +236,13 @@ pub fn update_ui(
             &mut *physics_context,
             &mut *physics_config,
             &mut *debug_render_context,
+            &mut new_render_mode,
+            &mut coloring.mode,
+            &mut overlays,
             &mut *operations,
             exit,
         );
+        render_mode.set_if_neq(new_render_mode);
         play_stop::ui(
             window,
             &cli,
@@ -198,6 +250,16 @@ pub fn update_ui(
             &mut ui_state,
             &mut *physics_context,
             &mut *physics_config,
+            &mut history,
+        );
+        timeline::ui(
+            window,
+            &cli,
+            &mut ui_context,
+            &mut ui_state,
+            &mut *physics_config,
+            &mut timeline,
+            &progress,
         );
         popup_menu::ui(
             window,
@@ -213,7 +275,26 @@ pub fn update_ui(
             &mut *physics_config,
             &mut *operations,
         );
+        #[cfg(feature = "dim3")]
+        mesh_import::ui(&mut ui_context, &mut ui_state, &mut operations);
         simulation_infos::ui(&mut ui_context, &mut ui_state, &*physics_context);
+        profiler::ui(&mut ui_context, &mut ui_state, &mut profiler);
+        color_legend::ui(&mut ui_context, &mut coloring);
+        array_generator::ui(
+            &mut ui_context,
+            &mut ui_state,
+            &mut operations,
+            &*physics_context,
+            &selections,
+        );
+        contacts_inspector::ui(
+            &mut ui_context,
+            &mut ui_state,
+            &mut contacts,
+            &mut commands,
+            &*physics_context,
+            &selections,
+        );
         right_panel::ui(
             &mut commands,
             window,
@@ -222,12 +303,17 @@ pub fn update_ui(
             &mut ui_state,
             &mut *physics_context,
             &mut *physics_config,
+            &mut history,
+            &mut operations,
             &mut bodies,
             &mut colliders,
             &mut character_controllers,
             &mut selections,
             &mut visibility,
             &mut transforms,
+            &names,
+            &mut impulse_joints,
+            &mut multibody_joints,
         );
     }
 }
@@ -4,6 +4,8 @@ use bevy_egui::egui::PointerButton;
 use bevy_egui::{egui, EguiContexts};
 use bevy_rapier::plugin::{RapierConfiguration, RapierContext};
 
+use crate::operation::SceneHistory;
+
 use super::{ButtonTexture, UiState};
 
 pub(super) fn ui(
@@ -13,6 +15,7 @@ pub(super) fn ui(
     ui_state: &mut UiState,
     _physics_context: &mut RapierContext,
     physics_config: &mut RapierConfiguration,
+    history: &mut SceneHistory,
 ) {
     if ui_state.single_step {
         ui_state.single_step = false;
@@ -33,7 +36,13 @@ pub(super) fn ui(
         .fixed_pos(pos)
         .show(ui_context.ctx_mut(), |ui| {
             ui.horizontal(|ui| {
-                let _ = ui.button(ButtonTexture::Undo.rich_text());
+                let undo_button = ui.add_enabled(
+                    history.can_undo(),
+                    egui::Button::new(ButtonTexture::Undo.rich_text()),
+                );
+                if undo_button.clicked() {
+                    history.undo();
+                }
 
                 let play_pause = if ui_state.running {
                     ButtonTexture::Pause
@@ -58,7 +67,13 @@ pub(super) fn ui(
                     ui_state.single_step = true;
                 }
 
-                let _ = ui.button(ButtonTexture::Redo.rich_text());
+                let redo_button = ui.add_enabled(
+                    history.can_redo(),
+                    egui::Button::new(ButtonTexture::Redo.rich_text()),
+                );
+                if redo_button.clicked() {
+                    history.redo();
+                }
             })
         });
 }
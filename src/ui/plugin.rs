@@ -1,5 +1,7 @@
 use super::{ActiveMouseAction, UiState};
+use crate::render::RenderSystems;
 use bevy::prelude::*;
+use bevy_rapier::plugin::PhysicsSet;
 
 /// Plugin responsible for creating an UI for interacting, monitoring, and modifying the simulation.
 pub struct RapierUiPlugin;
@@ -9,10 +11,36 @@ impl Plugin for RapierUiPlugin {
         app.add_plugins(bevy_egui::EguiPlugin)
             .insert_resource(UiState::default())
             .insert_resource(ActiveMouseAction::None)
+            .insert_resource(super::ProfilerHistory::default())
+            .insert_resource(super::ContactInspector::default())
             .add_systems(Startup, super::load_assets)
             .add_systems(PreUpdate, super::focus_ui)
             .add_systems(Update, super::add_missing_gizmos)
-            .add_systems(Update, super::update_ui)
-            .add_systems(Update, super::handle_keyboard_inputs);
+            .add_systems(
+                PostUpdate,
+                super::record_profile.after(PhysicsSet::StepSimulation),
+            )
+            .add_systems(
+                PostUpdate,
+                super::record_contact_events.after(PhysicsSet::Writeback),
+            )
+            .add_systems(
+                Update,
+                super::highlight_hovered_contact.after(super::update_ui),
+            )
+            // The UI and keyboard shortcuts push operations and history checkpoints that
+            // must be processed during the same frame.
+            .add_systems(
+                Update,
+                super::update_ui.before(RenderSystems::BeforeCommands),
+            )
+            .add_systems(
+                Update,
+                super::handle_keyboard_inputs.before(RenderSystems::BeforeCommands),
+            )
+            .add_systems(
+                Update,
+                super::handle_clipboard_inputs.before(RenderSystems::BeforeCommands),
+            );
     }
 }
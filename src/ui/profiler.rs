@@ -0,0 +1,234 @@
+use crate::ui::UiState;
+use bevy::prelude::*;
+use bevy_egui::egui::{self, Color32};
+use bevy_egui::EguiContexts;
+use bevy_rapier::plugin::{RapierConfiguration, RapierContext};
+use bevy_rapier::rapier::counters::Counters;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+
+/// Number of steps kept in the profiler history.
+const HISTORY_LEN: usize = 600;
+/// Height of the stacked timings graph.
+const GRAPH_HEIGHT: f32 = 120.0;
+
+/// The timing categories shown in the stacked graph, from bottom to top.
+const CATEGORIES: [(&str, Color32); 5] = [
+    ("Broad-phase", Color32::from_rgb(86, 180, 233)),
+    ("Narrow-phase", Color32::from_rgb(0, 158, 115)),
+    ("Solver", Color32::from_rgb(230, 159, 0)),
+    ("Island", Color32::from_rgb(204, 121, 167)),
+    ("CCD", Color32::from_rgb(213, 94, 0)),
+];
+
+/// The timings (in milliseconds) of a single simulation step.
+#[derive(Copy, Clone, Debug)]
+struct ProfileSample {
+    step: usize,
+    total: f64,
+    /// The timings of each of the `CATEGORIES`.
+    categories: [f64; 5],
+}
+
+impl ProfileSample {
+    fn new(step: usize, counters: &Counters) -> Self {
+        Self {
+            step,
+            total: counters.step_time(),
+            categories: [
+                counters.broad_phase_time(),
+                counters.narrow_phase_time(),
+                counters.solver_time(),
+                counters.island_construction_time(),
+                counters.ccd_time(),
+            ],
+        }
+    }
+}
+
+/// The timings of the last `HISTORY_LEN` simulation steps.
+#[derive(Resource, Default)]
+pub struct ProfilerHistory {
+    samples: VecDeque<ProfileSample>,
+    num_steps: usize,
+}
+
+impl ProfilerHistory {
+    fn push(&mut self, counters: &Counters) {
+        if self.samples.len() == HISTORY_LEN {
+            self.samples.pop_front();
+        }
+        self.samples
+            .push_back(ProfileSample::new(self.num_steps, counters));
+        self.num_steps += 1;
+    }
+
+    fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// The min, average, and max of the given timing over the whole history.
+    fn stats(&self, timing: impl Fn(&ProfileSample) -> f64) -> Option<[f64; 3]> {
+        let mut values = self.samples.iter().map(timing);
+        let first = values.next()?;
+        let [mut min, mut sum, mut max] = [first; 3];
+        for value in values {
+            min = min.min(value);
+            max = max.max(value);
+            sum += value;
+        }
+        Some([min, sum / self.samples.len() as f64, max])
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = String::from("step,total");
+        for (name, _) in CATEGORIES {
+            csv.push(',');
+            csv.push_str(name);
+        }
+        csv.push('\n');
+
+        for sample in &self.samples {
+            let _ = write!(csv, "{},{}", sample.step, sample.total);
+            for value in sample.categories {
+                let _ = write!(csv, ",{}", value);
+            }
+            csv.push('\n');
+        }
+
+        csv
+    }
+}
+
+/// Records the timings of the step that just ran.
+pub fn record_profile(
+    config: Res<RapierConfiguration>,
+    physics: Res<RapierContext>,
+    mut history: ResMut<ProfilerHistory>,
+) {
+    if config.physics_pipeline_active {
+        history.push(&physics.pipeline.counters);
+    }
+}
+
+pub(super) fn ui(
+    ui_context: &mut EguiContexts,
+    ui_state: &mut UiState,
+    history: &mut ProfilerHistory,
+) {
+    egui::Window::new("⏱ Profiler")
+        .open(&mut ui_state.profiler_open)
+        .resizable(false)
+        .show(ui_context.ctx_mut(), |ui| {
+            stacked_graph(ui, history);
+
+            egui::Grid::new("profiler stats")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("");
+                    ui.label("min");
+                    ui.label("avg");
+                    ui.label("max");
+                    ui.end_row();
+
+                    let stats_row = |ui: &mut egui::Ui,
+                                     label: &str,
+                                     color: Color32,
+                                     stats: Option<[f64; 3]>| {
+                        ui.colored_label(color, label);
+                        for value in stats.unwrap_or_default() {
+                            ui.label(format!("{:.2}ms", value));
+                        }
+                        ui.end_row();
+                    };
+
+                    for (i, (name, color)) in CATEGORIES.iter().enumerate() {
+                        stats_row(ui, *name, *color, history.stats(|s| s.categories[i]));
+                    }
+                    let text_color = ui.visuals().text_color();
+                    stats_row(ui, "Total", text_color, history.stats(|s| s.total));
+                });
+
+            ui.horizontal(|ui| {
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui.button("💾 Export CSV…").clicked() {
+                    if let Err(e) = export_csv(history) {
+                        error!("Failed to export profiler history: {:?}", e);
+                    }
+                }
+                if ui.button("Clear").clicked() {
+                    history.clear();
+                }
+            });
+        });
+}
+
+/// Plots the timings of each category stacked on top of each other, one column per step.
+fn stacked_graph(ui: &mut egui::Ui, history: &ProfilerHistory) {
+    let width = ui.available_width().max(HISTORY_LEN as f32 / 2.0);
+    let (rect, response) =
+        ui.allocate_exact_size(egui::vec2(width, GRAPH_HEIGHT), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+
+    let max_total = history
+        .samples
+        .iter()
+        .map(|s| s.categories.iter().sum::<f64>())
+        .fold(0.0, f64::max);
+    if max_total <= 0.0 {
+        return;
+    }
+
+    let column_width = rect.width() / HISTORY_LEN as f32;
+    let y_scale = rect.height() / max_total as f32;
+
+    for (i, sample) in history.samples.iter().enumerate() {
+        let x = rect.left() + i as f32 * column_width;
+        let mut y = rect.bottom();
+
+        for (value, (_, color)) in sample.categories.iter().zip(CATEGORIES) {
+            let height = *value as f32 * y_scale;
+            if height > 0.0 {
+                let column = egui::Rect::from_min_max(
+                    egui::pos2(x, y - height),
+                    egui::pos2(x + column_width, y),
+                );
+                painter.rect_filled(column, 0.0, color);
+            }
+            y -= height;
+        }
+    }
+
+    painter.text(
+        rect.left_top() + egui::vec2(2.0, 2.0),
+        egui::Align2::LEFT_TOP,
+        format!("{:.2}ms", max_total),
+        egui::FontId::monospace(10.0),
+        ui.visuals().text_color(),
+    );
+
+    // Show the timings of the hovered step.
+    if let Some(pos) = response.hover_pos() {
+        let i = ((pos.x - rect.left()) / column_width) as usize;
+        if let Some(sample) = history.samples.get(i) {
+            response.on_hover_ui_at_pointer(|ui| {
+                ui.label(format!("Step {}: {:.2}ms", sample.step, sample.total));
+                for (value, (name, color)) in sample.categories.iter().zip(CATEGORIES) {
+                    ui.colored_label(color, format!("{}: {:.2}ms", name, value));
+                }
+            });
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn export_csv(history: &ProfilerHistory) -> anyhow::Result<()> {
+    if let Some(path) = native_dialog::FileDialog::new()
+        .add_filter("CSV", &["csv"])
+        .show_save_single_file()?
+    {
+        std::fs::write(path, history.to_csv())?;
+    }
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use crate::cli::CliArgs;
 use crate::control::CharacterControlOptions;
+use crate::operation::{Operations, SceneHistory};
 use crate::selection::Selection;
 use crate::utils::{ColliderComponentsMut, RigidBodyComponentsMut};
 use bevy::prelude::*;
@@ -7,7 +8,9 @@ use bevy::window::Window;
 use bevy_egui::{egui, EguiContexts};
 use bevy_rapier::prelude::*;
 
-use super::{OpenObjectTab, UiState};
+use super::contacts_inspector::selected_colliders;
+use super::material_inspector::{self, MaterialPresets};
+use super::{joint_inspector, scene_explorer, OpenObjectTab, UiState};
 
 pub(super) fn ui(
     commands: &mut Commands,
@@ -15,8 +18,10 @@ pub(super) fn ui(
     cli: &CliArgs,
     ui_context: &mut EguiContexts,
     ui_state: &mut UiState,
-    _physics_context: &mut RapierContext,
+    physics_context: &mut RapierContext,
     _physics_config: &mut RapierConfiguration,
+    history: &mut SceneHistory,
+    operations: &mut Operations,
     bodies: &mut Query<RigidBodyComponentsMut>,
     colliders: &mut Query<ColliderComponentsMut>,
     character_controllers: &mut Query<(
@@ -26,6 +31,9 @@ pub(super) fn ui(
     selections: &mut Query<(Entity, &mut Selection)>,
     visibility: &mut Query<(Entity, &mut Visibility)>,
     transforms: &mut Query<(Entity, &mut Transform)>,
+    names: &Query<&Name>,
+    impulse_joints: &mut Query<(Entity, &mut ImpulseJoint, Option<&Parent>)>,
+    multibody_joints: &mut Query<(Entity, &mut MultibodyJoint)>,
 ) {
     if cli.lower_graphics {
         return;
@@ -35,7 +43,21 @@ pub(super) fn ui(
         .default_width(300.0)
         .resizable(false)
         .show(ui_context.ctx_mut(), |ui| {
-            scene_explorer(commands, ui, bodies, colliders, selections, visibility);
+            scene_explorer::ui(
+                commands,
+                ui,
+                &mut ui_state.scene_explorer,
+                history,
+                operations,
+                physics_context,
+                bodies,
+                colliders,
+                selections,
+                visibility,
+                names,
+                impulse_joints,
+                multibody_joints,
+            );
             ui.separator();
 
             ui.horizontal(|ui| {
@@ -57,72 +79,57 @@ pub(super) fn ui(
                 selection_inspector(
                     commands,
                     ui,
+                    history,
+                    operations,
+                    physics_context,
+                    &mut ui_state.materials,
                     bodies,
                     colliders,
                     character_controllers,
                     selections,
                     transforms,
+                    impulse_joints,
+                    multibody_joints,
+                );
+            } else {
+                material_inspector::new_body_ui(
+                    ui,
+                    &ui_state.materials,
+                    &mut ui_state.new_body_material,
                 );
             }
         });
 }
 
-fn scene_explorer(
-    commands: &mut Commands,
-    ui: &mut egui::Ui,
-    bodies: &mut Query<RigidBodyComponentsMut>,
-    colliders: &mut Query<ColliderComponentsMut>,
-    selections: &mut Query<(Entity, &mut Selection)>,
-    visibility: &mut Query<(Entity, &mut Visibility)>,
-) {
-    ui.heading("Scene explorer");
-
-    egui::ScrollArea::vertical()
-        .max_height(200.0)
-        .show(ui, |ui| {
-            for (entity, ..) in bodies.iter() {
-                let is_selected = selections
-                    .get(entity)
-                    .map(|sel| sel.1.selected())
-                    .unwrap_or(false);
-                ui.horizontal(|ui| {
-                    egui::containers::CollapsingHeader::new(format!("Body {:?}", entity)).show(
-                        ui,
-                        |ui| {
-                            if let Ok(_collider) = colliders.get(entity) {
-                                ui.label(format!("Collider {:?}", entity));
-                            }
-                        },
-                    );
-
-                    let is_visible = visibility
-                        .get(entity)
-                        .map(|v| v.1 != Visibility::Hidden)
-                        .unwrap_or(true);
-                    let visibility_icon = if is_visible { "🌑" } else { "🌕" };
-                    if ui.button(visibility_icon).clicked() {
-                        commands.entity(entity).insert(if is_visible {
-                            Visibility::Hidden
-                        } else {
-                            Visibility::Visible
-                        });
-
-                        if is_visible {
-                            // We hide the object, so it can’t remain selected.
-                            commands.entity(entity).remove::<Selection>();
-                        }
-                    }
-                    if ui.button("❌").clicked() {
-                        commands.entity(entity).despawn_recursive();
-                    }
-                });
-            }
-        });
+const BODY_TYPES: [(RigidBody, &str); 4] = [
+    (RigidBody::Dynamic, "Dynamic"),
+    (RigidBody::Fixed, "Fixed"),
+    (RigidBody::KinematicPositionBased, "KinematicPositionBased"),
+    (RigidBody::KinematicVelocityBased, "KinematicVelocityBased"),
+];
+
+/// Number of collision groups that can be edited from the inspector.
+const COLLISION_GROUP_BITS: usize = 4;
+
+/// Requests a history checkpoint if a widget starts modifying the scene, and returns whether
+/// it modified it.
+///
+/// Dragging a value modifies the scene on every frame, so only the start of the drag is
+/// recorded.
+pub(super) fn track_edit(history: &mut SceneHistory, response: egui::Response) -> bool {
+    if response.drag_started() || (response.changed() && !response.dragged()) {
+        history.checkpoint();
+    }
+    response.changed()
 }
 
 fn selection_inspector(
     commands: &mut Commands,
     ui: &mut egui::Ui,
+    history: &mut SceneHistory,
+    operations: &mut Operations,
+    physics: &RapierContext,
+    materials: &mut MaterialPresets,
     bodies: &mut Query<RigidBodyComponentsMut>,
     colliders: &mut Query<ColliderComponentsMut>,
     character_controllers: &mut Query<(
@@ -131,7 +138,22 @@ fn selection_inspector(
     )>,
     selections: &mut Query<(Entity, &mut Selection)>,
     transforms: &mut Query<(Entity, &mut Transform)>,
+    impulse_joints: &mut Query<(Entity, &mut ImpulseJoint, Option<&Parent>)>,
+    multibody_joints: &mut Query<(Entity, &mut MultibodyJoint)>,
 ) {
+    let selected: Vec<_> = selections
+        .iter()
+        .filter(|(_, selection)| selection.selected())
+        .map(|(entity, _)| entity)
+        .collect();
+
+    if selected.len() > 1 {
+        group_inspector(
+            commands, ui, history, physics, materials, bodies, colliders, &selected,
+        );
+        return;
+    }
+
     let mut selected_any = false;
     for (entity, selected) in selections.iter() {
         if selected.selected() {
@@ -157,24 +179,18 @@ fn selection_inspector(
                     egui::ComboBox::from_id_source("Rigid-body type")
                         .selected_text(format!("{:?}", *rb))
                         .show_ui(ui, |ui| {
-                            ui.selectable_value(&mut *rb, RigidBody::Dynamic, "Dynamic");
-                            ui.selectable_value(&mut *rb, RigidBody::Fixed, "Fixed");
-                            ui.selectable_value(
-                                &mut *rb,
-                                RigidBody::KinematicPositionBased,
-                                "KinematicPositionBased",
-                            );
-                            ui.selectable_value(
-                                &mut *rb,
-                                RigidBody::KinematicVelocityBased,
-                                "KinematicVelocityBased",
-                            );
+                            for (body_type, label) in BODY_TYPES {
+                                track_edit(
+                                    history,
+                                    ui.selectable_value(&mut *rb, body_type, label),
+                                );
+                            }
                         });
                 });
 
                 egui::Grid::new("Rigid-body props").show(ui, |ui| {
                     let mut enabled = disabled.is_none();
-                    if ui.checkbox(&mut enabled, "Enabled").changed() {
+                    if track_edit(history, ui.checkbox(&mut enabled, "Enabled")) {
                         if enabled {
                             commands.entity(entity).remove::<RigidBodyDisabled>();
                         } else {
@@ -187,19 +203,25 @@ fn selection_inspector(
                         {
                             ui.label("Position: ");
                             let mut translation = transform.translation;
-                            let mut changed =
-                                ui.add(egui::DragValue::new(&mut translation.x)).changed();
-                            changed = ui.add(egui::DragValue::new(&mut translation.y)).changed()
-                                || changed;
+                            let mut changed = track_edit(
+                                history,
+                                ui.add(egui::DragValue::new(&mut translation.x)),
+                            );
+                            changed |= track_edit(
+                                history,
+                                ui.add(egui::DragValue::new(&mut translation.y)),
+                            );
 
                             #[cfg(feature = "dim3")]
                             {
-                                changed =
-                                    ui.add(egui::DragValue::new(&mut translation.z)).changed()
-                                        || changed;
+                                changed |= track_edit(
+                                    history,
+                                    ui.add(egui::DragValue::new(&mut translation.z)),
+                                );
                             }
 
                             if ui.button("clear").clicked() {
+                                history.checkpoint();
                                 translation = Vec3::ZERO;
                                 changed = true;
                             }
@@ -223,9 +245,10 @@ fn selection_inspector(
                             let mut angle = transform.rotation.to_scaled_axis().z;
 
                             ui.label("Rotation: ");
-                            let mut changed = ui.drag_angle(&mut angle).changed();
+                            let mut changed = track_edit(history, ui.drag_angle(&mut angle));
 
                             if ui.button("clear").clicked() {
+                                history.checkpoint();
                                 angle = 0.0;
                                 changed = true;
                             }
@@ -241,11 +264,12 @@ fn selection_inspector(
                             let mut axisangle = transform.rotation.to_scaled_axis();
 
                             ui.label("Rotation: ");
-                            let mut changed = ui.drag_angle(&mut axisangle.x).changed();
-                            changed = ui.drag_angle(&mut axisangle.y).changed() || changed;
-                            changed = ui.drag_angle(&mut axisangle.z).changed() || changed;
+                            let mut changed = track_edit(history, ui.drag_angle(&mut axisangle.x));
+                            changed |= track_edit(history, ui.drag_angle(&mut axisangle.y));
+                            changed |= track_edit(history, ui.drag_angle(&mut axisangle.z));
 
                             if ui.button("clear").clicked() {
+                                history.checkpoint();
                                 axisangle = Vect::ZERO;
                                 changed = true;
                             }
@@ -263,13 +287,23 @@ fn selection_inspector(
 
                             {
                                 ui.label("Lin. velocity: ");
-                                ui.add(egui::DragValue::new(&mut new_vel.linvel.x));
-                                ui.add(egui::DragValue::new(&mut new_vel.linvel.y));
+                                track_edit(
+                                    history,
+                                    ui.add(egui::DragValue::new(&mut new_vel.linvel.x)),
+                                );
+                                track_edit(
+                                    history,
+                                    ui.add(egui::DragValue::new(&mut new_vel.linvel.y)),
+                                );
                                 #[cfg(feature = "dim3")]
                                 {
-                                    ui.add(egui::DragValue::new(&mut new_vel.linvel.z));
+                                    track_edit(
+                                        history,
+                                        ui.add(egui::DragValue::new(&mut new_vel.linvel.z)),
+                                    );
                                 }
                                 if ui.button("clear").clicked() {
+                                    history.checkpoint();
                                     new_vel.linvel = Vect::ZERO;
                                 }
                             }
@@ -278,18 +312,32 @@ fn selection_inspector(
                             #[cfg(feature = "dim2")]
                             {
                                 ui.label("Ang. velocity: ");
-                                ui.add(egui::DragValue::new(&mut new_vel.angvel));
+                                track_edit(
+                                    history,
+                                    ui.add(egui::DragValue::new(&mut new_vel.angvel)),
+                                );
                                 if ui.button("clear").clicked() {
+                                    history.checkpoint();
                                     new_vel.angvel = 0.0;
                                 }
                             }
                             #[cfg(feature = "dim3")]
                             {
                                 ui.label("Ang. velocity: ");
-                                ui.add(egui::DragValue::new(&mut new_vel.angvel.x));
-                                ui.add(egui::DragValue::new(&mut new_vel.angvel.y));
-                                ui.add(egui::DragValue::new(&mut new_vel.angvel.z));
+                                track_edit(
+                                    history,
+                                    ui.add(egui::DragValue::new(&mut new_vel.angvel.x)),
+                                );
+                                track_edit(
+                                    history,
+                                    ui.add(egui::DragValue::new(&mut new_vel.angvel.y)),
+                                );
+                                track_edit(
+                                    history,
+                                    ui.add(egui::DragValue::new(&mut new_vel.angvel.z)),
+                                );
                                 if ui.button("clear").clicked() {
+                                    history.checkpoint();
                                     new_vel.angvel = Vect::ZERO;
                                 }
                             }
@@ -308,12 +356,12 @@ fn selection_inspector(
                             #[cfg(feature = "dim3")]
                             let mut z = locked_axes.contains(LockedAxes::TRANSLATION_LOCKED_Z);
                             ui.label("Lin. lock: ");
-                            ui.checkbox(&mut x, "x");
-                            ui.checkbox(&mut y, "y");
+                            track_edit(history, ui.checkbox(&mut x, "x"));
+                            track_edit(history, ui.checkbox(&mut y, "y"));
 
                             #[cfg(feature = "dim3")]
                             {
-                                ui.checkbox(&mut z, "z");
+                                track_edit(history, ui.checkbox(&mut z, "z"));
                             }
 
                             locked_axes.set(LockedAxes::TRANSLATION_LOCKED_X, x);
@@ -330,7 +378,7 @@ fn selection_inspector(
                         {
                             let mut x = locked_axes.contains(LockedAxes::ROTATION_LOCKED);
                             ui.label("Ang. lock: ");
-                            ui.checkbox(&mut x, "θ");
+                            track_edit(history, ui.checkbox(&mut x, "θ"));
                             locked_axes.set(LockedAxes::ROTATION_LOCKED, x);
                         }
 
@@ -340,9 +388,9 @@ fn selection_inspector(
                             let mut y = locked_axes.contains(LockedAxes::ROTATION_LOCKED_Y);
                             let mut z = locked_axes.contains(LockedAxes::ROTATION_LOCKED_Z);
                             ui.label("Ang. lock: ");
-                            ui.checkbox(&mut x, "θx");
-                            ui.checkbox(&mut y, "θy");
-                            ui.checkbox(&mut z, "θz");
+                            track_edit(history, ui.checkbox(&mut x, "θx"));
+                            track_edit(history, ui.checkbox(&mut y, "θy"));
+                            track_edit(history, ui.checkbox(&mut z, "θz"));
                             locked_axes.set(LockedAxes::ROTATION_LOCKED_X, x);
                             locked_axes.set(LockedAxes::ROTATION_LOCKED_Y, y);
                             locked_axes.set(LockedAxes::ROTATION_LOCKED_Z, z);
@@ -356,11 +404,11 @@ fn selection_inspector(
                             && sleep_state.angular_threshold > 0.0;
 
                         ui.label("Sleeping: ");
-                        if ui.checkbox(&mut sleeping, "").changed() {
+                        if track_edit(history, ui.checkbox(&mut sleeping, "")) {
                             sleep_state.sleeping = sleeping;
                         }
                         ui.label("Can sleep: ");
-                        if ui.checkbox(&mut can_sleep, "").changed() {
+                        if track_edit(history, ui.checkbox(&mut can_sleep, "")) {
                             if can_sleep {
                                 **sleep_state = Sleeping {
                                     sleeping,
@@ -390,21 +438,20 @@ fn selection_inspector(
                 if let Some(ccd) = ccd.as_mut() {
                     ui.horizontal(|ui| {
                         ui.label("CCD: ");
-                        ui.checkbox(&mut ccd.enabled, "");
+                        track_edit(history, ui.checkbox(&mut ccd.enabled, ""));
                     });
                 }
             }
 
-            if let Ok((_entity, _collider, _sensor, _mprops, mut coll_groups, _disabled)) =
+            if let Ok((_entity, _collider, _sensor, _mprops, mut coll_groups, ..)) =
                 colliders.get_mut(entity)
             {
                 egui::Grid::new("Collider props").show(ui, |ui| {
                     if let Some(coll_groups) = &mut coll_groups {
-                        const BITS: usize = 4;
-                        let mut gbits = [false; BITS];
-                        let mut fbits = [false; BITS];
+                        let mut gbits = [false; COLLISION_GROUP_BITS];
+                        let mut fbits = [false; COLLISION_GROUP_BITS];
 
-                        for k in 0..BITS {
+                        for k in 0..COLLISION_GROUP_BITS {
                             if coll_groups.memberships.bits() & (1 << k) != 0 {
                                 gbits[k] = true;
                             }
@@ -416,7 +463,7 @@ fn selection_inspector(
                         ui.label("Coll. groups:  ");
                         ui.horizontal(|ui| {
                             for bit in &mut gbits {
-                                ui.checkbox(bit, "");
+                                track_edit(history, ui.checkbox(bit, ""));
                             }
                         });
                         ui.end_row();
@@ -424,7 +471,7 @@ fn selection_inspector(
                         ui.label("Coll. filters: ");
                         ui.horizontal(|ui| {
                             for bit in &mut fbits {
-                                ui.checkbox(bit, "");
+                                track_edit(history, ui.checkbox(bit, ""));
                             }
                         });
                         ui.end_row();
@@ -432,7 +479,7 @@ fn selection_inspector(
                         let mut new_memberships = 0;
                         let mut new_filters = 0;
 
-                        for k in 0..BITS {
+                        for k in 0..COLLISION_GROUP_BITS {
                             if gbits[k] {
                                 new_memberships |= 1 << k;
                             }
@@ -453,24 +500,50 @@ fn selection_inspector(
                 });
             }
 
+            ui.separator();
+            // The material of a body is the one of its colliders.
+            let material_targets: Vec<_> = selected_colliders(physics, entity)
+                .into_iter()
+                .map(|(collider, _)| collider)
+                .collect();
+            material_inspector::ui(
+                commands,
+                ui,
+                history,
+                materials,
+                &material_targets,
+                colliders,
+            );
+
             ui.separator();
             egui::Grid::new("Character controller props").show(ui, |ui| {
                 let character_length_val =
-                    |ui: &mut egui::Ui, label: &str, value: &mut CharacterLength| {
+                    |ui: &mut egui::Ui,
+                     history: &mut SceneHistory,
+                     label: &str,
+                     value: &mut CharacterLength| {
                         match value {
                             CharacterLength::Absolute(val) => {
                                 ui.label(format!("{label}"));
-                                ui.add(
-                                    egui::DragValue::new(val).clamp_range(0.1..=10.0).speed(0.1),
+                                track_edit(
+                                    history,
+                                    ui.add(
+                                        egui::DragValue::new(val)
+                                            .clamp_range(0.1..=10.0)
+                                            .speed(0.1),
+                                    ),
                                 );
                             }
                             CharacterLength::Relative(val) => {
                                 ui.label(format!("{label} (%)"));
                                 let mut val_percent = *val * 100.0;
-                                ui.add(
-                                    egui::DragValue::new(&mut val_percent)
-                                        .clamp_range(1.0..=100.0)
-                                        .speed(1.0),
+                                track_edit(
+                                    history,
+                                    ui.add(
+                                        egui::DragValue::new(&mut val_percent)
+                                            .clamp_range(1.0..=100.0)
+                                            .speed(1.0),
+                                    ),
                                 );
                                 *val = val_percent / 100.0;
                             }
@@ -480,63 +553,85 @@ fn selection_inspector(
 
                 if let Ok((mut controller, mut options)) = character_controllers.get_mut(entity) {
                     ui.label("Character controller: ");
-                    ui.checkbox(&mut options.enabled, "");
+                    track_edit(history, ui.checkbox(&mut options.enabled, ""));
                     ui.end_row();
 
                     if options.enabled {
                         ui.label("Gravity scale:");
-                        ui.add(
-                            egui::DragValue::new(&mut options.gravity_scale)
-                                .clamp_range(0.0..=20.0)
-                                .speed(1.0),
+                        track_edit(
+                            history,
+                            ui.add(
+                                egui::DragValue::new(&mut options.gravity_scale)
+                                    .clamp_range(0.0..=20.0)
+                                    .speed(1.0),
+                            ),
                         );
                         ui.end_row();
 
-                        character_length_val(ui, "Offset", &mut controller.offset);
+                        character_length_val(ui, history, "Offset", &mut controller.offset);
 
                         ui.label("Slide");
-                        ui.checkbox(&mut controller.slide, "");
+                        track_edit(history, ui.checkbox(&mut controller.slide, ""));
                         ui.end_row();
 
                         if controller.slide {
                             if let Some(autostep) = &mut controller.autostep {
                                 character_length_val(
                                     ui,
+                                    history,
                                     "Max autostep height",
                                     &mut autostep.max_height,
                                 );
                                 character_length_val(
                                     ui,
+                                    history,
                                     "Min autostep width",
                                     &mut autostep.min_width,
                                 );
 
                                 ui.label("Autostep on dynamic bodies");
-                                ui.checkbox(&mut autostep.include_dynamic_bodies, "");
+                                track_edit(
+                                    history,
+                                    ui.checkbox(&mut autostep.include_dynamic_bodies, ""),
+                                );
                                 ui.end_row();
                             }
 
                             ui.label("Max slope climb angle: ");
-                            ui.drag_angle(&mut controller.max_slope_climb_angle);
+                            track_edit(
+                                history,
+                                ui.drag_angle(&mut controller.max_slope_climb_angle),
+                            );
                             ui.end_row();
 
                             ui.label("Min slope slide angle: ");
-                            ui.drag_angle(&mut controller.min_slope_slide_angle);
+                            track_edit(
+                                history,
+                                ui.drag_angle(&mut controller.min_slope_slide_angle),
+                            );
                             ui.end_row();
 
                             ui.label("Move dyn. rigid-bodies");
-                            ui.checkbox(&mut controller.apply_impulse_to_dynamic_bodies, "");
+                            track_edit(
+                                history,
+                                ui.checkbox(&mut controller.apply_impulse_to_dynamic_bodies, ""),
+                            );
                             ui.end_row();
 
                             if let Some(snap_height) = &mut controller.snap_to_ground {
-                                character_length_val(ui, "Max snap-to-ground height", snap_height);
+                                character_length_val(
+                                    ui,
+                                    history,
+                                    "Max snap-to-ground height",
+                                    snap_height,
+                                );
                             }
                         }
                     }
                 } else {
                     let mut has_character_controller = false;
                     ui.label("Character controller: ");
-                    if ui.checkbox(&mut has_character_controller, "").changed() {
+                    if track_edit(history, ui.checkbox(&mut has_character_controller, "")) {
                         commands
                             .entity(entity)
                             .insert(KinematicCharacterController::default())
@@ -545,6 +640,17 @@ fn selection_inspector(
                     }
                 }
             });
+
+            ui.separator();
+            joint_inspector::ui(
+                commands,
+                ui,
+                history,
+                operations,
+                entity,
+                impulse_joints,
+                multibody_joints,
+            );
         }
     }
 
@@ -552,3 +658,185 @@ fn selection_inspector(
         ui.label("Select an object to see its properties here.");
     }
 }
+
+/// Edits the properties shared by all the selected objects at once. A property displayed as
+/// “mixed” has different values among the selected objects, and is overwritten on all of them
+/// when modified.
+fn group_inspector(
+    commands: &mut Commands,
+    ui: &mut egui::Ui,
+    history: &mut SceneHistory,
+    physics: &RapierContext,
+    materials: &MaterialPresets,
+    bodies: &mut Query<RigidBodyComponentsMut>,
+    colliders: &mut Query<ColliderComponentsMut>,
+    selected: &[Entity],
+) {
+    ui.label(format!("{} objects selected.", selected.len()));
+
+    let body_types: Vec<_> = selected
+        .iter()
+        .filter_map(|entity| bodies.get(*entity).ok())
+        .map(|(_, rb, ..)| *rb)
+        .collect();
+    let ccd_enabled: Vec<_> = selected
+        .iter()
+        .filter_map(|entity| bodies.get(*entity).ok())
+        .map(|(.., ccd, _, _, _, _)| ccd.map(|ccd| ccd.enabled).unwrap_or(false))
+        .collect();
+
+    if let Some(first_type) = body_types.first() {
+        let common_type = body_types
+            .iter()
+            .all(|t| t == first_type)
+            .then_some(*first_type);
+        let mut new_type = None;
+
+        ui.horizontal(|ui| {
+            ui.label("Rigid-body type: ");
+            egui::ComboBox::from_id_source("Group rigid-body type")
+                .selected_text(
+                    common_type
+                        .map(|t| format!("{:?}", t))
+                        .unwrap_or_else(|| "Mixed".to_string()),
+                )
+                .show_ui(ui, |ui| {
+                    for (body_type, label) in BODY_TYPES {
+                        if ui
+                            .selectable_label(common_type == Some(body_type), label)
+                            .clicked()
+                        {
+                            history.checkpoint();
+                            new_type = Some(body_type);
+                        }
+                    }
+                });
+        });
+
+        let mut ccd = ccd_enabled.iter().all(|enabled| *enabled);
+        let ccd_mixed = !ccd && ccd_enabled.iter().any(|enabled| *enabled);
+        let ccd_changed = ui
+            .horizontal(|ui| {
+                ui.label("CCD: ");
+                track_edit(
+                    history,
+                    ui.checkbox(&mut ccd, if ccd_mixed { "(mixed)" } else { "" }),
+                )
+            })
+            .inner;
+
+        for entity in selected {
+            let Ok((_, mut rb, .., mut body_ccd, _, _, _, _)) = bodies.get_mut(*entity) else {
+                continue;
+            };
+
+            if let Some(new_type) = new_type {
+                *rb = new_type;
+            }
+
+            if ccd_changed {
+                match body_ccd.as_mut() {
+                    Some(body_ccd) => body_ccd.enabled = ccd,
+                    None => {
+                        commands.entity(*entity).insert(Ccd { enabled: ccd });
+                    }
+                }
+            }
+        }
+    }
+
+    // The material of a body is the one of its colliders.
+    let material_targets: Vec<_> = selected
+        .iter()
+        .flat_map(|entity| selected_colliders(physics, *entity))
+        .map(|(collider, _)| collider)
+        .collect();
+    if !material_targets.is_empty() {
+        if let Some(material) = materials.menu(ui, "Apply material preset") {
+            history.checkpoint();
+            for collider in &material_targets {
+                material_inspector::apply(commands, colliders, *collider, &material);
+            }
+        }
+    }
+
+    // Colliders without collision groups interact with all the groups.
+    let groups: Vec<_> = selected
+        .iter()
+        .filter_map(|entity| colliders.get(*entity).ok())
+        .map(|(_, _, _, _, groups, ..)| groups.copied().unwrap_or_default())
+        .collect();
+
+    if groups.is_empty() {
+        return;
+    }
+
+    let all_have = |bits: fn(&CollisionGroups) -> u32, k: usize| {
+        groups.iter().all(|g| bits(g) & (1 << k) != 0)
+    };
+    let any_has = |bits: fn(&CollisionGroups) -> u32, k: usize| {
+        groups.iter().any(|g| bits(g) & (1 << k) != 0)
+    };
+    let memberships: fn(&CollisionGroups) -> u32 = |g| g.memberships.bits();
+    let filters: fn(&CollisionGroups) -> u32 = |g| g.filters.bits();
+
+    // The bits toggled by the user, with their new value.
+    let mut toggled_memberships = vec![];
+    let mut toggled_filters = vec![];
+
+    egui::Grid::new("Group collider props").show(ui, |ui| {
+        for (label, bits, toggled) in [
+            ("Coll. groups:  ", memberships, &mut toggled_memberships),
+            ("Coll. filters: ", filters, &mut toggled_filters),
+        ] {
+            ui.label(label);
+            ui.horizontal(|ui| {
+                for k in 0..COLLISION_GROUP_BITS {
+                    let mut value = all_have(bits, k);
+                    let mixed = !value && any_has(bits, k);
+                    let mut response = ui.checkbox(&mut value, "");
+                    if mixed {
+                        response = response.on_hover_text("Mixed");
+                    }
+                    if track_edit(history, response) {
+                        toggled.push((k, value));
+                    }
+                }
+            });
+            ui.end_row();
+        }
+    });
+
+    if toggled_memberships.is_empty() && toggled_filters.is_empty() {
+        return;
+    }
+
+    let toggle = |group: Group, toggled: &[(usize, bool)]| {
+        toggled.iter().fold(group, |group, (k, value)| {
+            let bit = Group::from_bits_truncate(1 << k);
+            if *value {
+                group | bit
+            } else {
+                group - bit
+            }
+        })
+    };
+
+    for entity in selected {
+        let Ok((_, _, _, _, coll_groups, ..)) = colliders.get_mut(*entity) else {
+            continue;
+        };
+        let old_groups = coll_groups.as_deref().copied().unwrap_or_default();
+        let new_groups = CollisionGroups {
+            memberships: toggle(old_groups.memberships, &toggled_memberships),
+            filters: toggle(old_groups.filters, &toggled_filters),
+        };
+
+        match coll_groups {
+            Some(mut coll_groups) => *coll_groups = new_groups,
+            None => {
+                commands.entity(*entity).insert(new_groups);
+            }
+        }
+    }
+}
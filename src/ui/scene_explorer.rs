@@ -0,0 +1,350 @@
+use crate::operation::{Operation, Operations, SceneHistory};
+use crate::selection::Selection;
+use crate::utils::{ColliderComponentsMut, RigidBodyComponentsMut};
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+use bevy_egui::egui;
+use bevy_rapier::prelude::*;
+
+/// Width of one level of indentation in the tree.
+const INDENT: f32 = 12.0;
+
+/// The state of the scene explorer tree.
+#[derive(Default)]
+pub struct SceneExplorerState {
+    /// Only the objects with a name containing this text are listed.
+    pub filter: String,
+    /// The bodies with their children shown.
+    pub expanded: HashSet<Entity>,
+    /// The entity being renamed, with the name being edited.
+    pub renaming: Option<(Entity, String)>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum RowKind {
+    Body,
+    Collider,
+    ImpulseJoint,
+    MultibodyJoint,
+}
+
+/// A line of the flattened scene tree.
+struct Row {
+    entity: Entity,
+    /// The entity selected when clicking this row.
+    selectable: Entity,
+    kind: RowKind,
+    depth: usize,
+    label: String,
+    /// Does this row have children, and are they shown?
+    expanded: Option<bool>,
+}
+
+pub(super) fn ui(
+    commands: &mut Commands,
+    ui: &mut egui::Ui,
+    state: &mut SceneExplorerState,
+    history: &mut SceneHistory,
+    operations: &mut Operations,
+    physics: &RapierContext,
+    bodies: &Query<RigidBodyComponentsMut>,
+    colliders: &Query<ColliderComponentsMut>,
+    selections: &mut Query<(Entity, &mut Selection)>,
+    visibility: &Query<(Entity, &mut Visibility)>,
+    names: &Query<&Name>,
+    impulse_joints: &Query<(Entity, &mut ImpulseJoint, Option<&Parent>)>,
+    multibody_joints: &Query<(Entity, &mut MultibodyJoint)>,
+) {
+    ui.heading("Scene explorer");
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut state.filter)
+                .hint_text("🔍 Filter…")
+                .desired_width(120.0),
+        );
+
+        let select_all = ui.button("Select all").on_hover_text("Ctrl+A").clicked();
+        let invert = ui.button("Invert").on_hover_text("Ctrl+I").clicked();
+        if select_all || invert {
+            for (entity, mut selection) in selections.iter_mut() {
+                let hidden = visibility
+                    .get(entity)
+                    .map(|v| v.1 == Visibility::Hidden)
+                    .unwrap_or(false);
+                if !hidden {
+                    selection.selected = select_all || !selection.selected;
+                }
+            }
+        }
+    });
+
+    let rows = tree_rows(
+        state,
+        physics,
+        bodies,
+        colliders,
+        names,
+        impulse_joints,
+        multibody_joints,
+    );
+    let row_height = ui.spacing().interact_size.y;
+
+    // Only the visible rows are laid out, so this stays fast with many entities.
+    egui::ScrollArea::vertical()
+        .max_height(200.0)
+        .auto_shrink([false, true])
+        .show_rows(ui, row_height, rows.len(), |ui, range| {
+            for row in &rows[range] {
+                ui.horizontal(|ui| {
+                    row_ui(
+                        commands, ui, state, history, operations, row, selections, visibility,
+                    );
+                });
+            }
+        });
+}
+
+/// Shows a row of the tree.
+#[allow(clippy::too_many_arguments)]
+fn row_ui(
+    commands: &mut Commands,
+    ui: &mut egui::Ui,
+    state: &mut SceneExplorerState,
+    history: &mut SceneHistory,
+    operations: &mut Operations,
+    row: &Row,
+    selections: &mut Query<(Entity, &mut Selection)>,
+    visibility: &Query<(Entity, &mut Visibility)>,
+) {
+    ui.add_space(row.depth as f32 * INDENT);
+
+    match row.expanded {
+        Some(expanded) => {
+            if ui.small_button(if expanded { "⏷" } else { "⏵" }).clicked() {
+                if expanded {
+                    state.expanded.remove(&row.entity);
+                } else {
+                    state.expanded.insert(row.entity);
+                }
+            }
+        }
+        None => {
+            ui.add_space(ui.spacing().interact_size.y);
+        }
+    }
+
+    match &mut state.renaming {
+        Some((entity, name)) if *entity == row.entity => {
+            let response = ui.text_edit_singleline(name);
+            if response.lost_focus() {
+                if !ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    commands.entity(row.entity).insert(Name::new(name.clone()));
+                }
+                state.renaming = None;
+            } else if !response.has_focus() {
+                response.request_focus();
+            }
+        }
+        _ => {
+            let is_selected = selections
+                .get(row.entity)
+                .map(|(_, sel)| sel.selected())
+                .unwrap_or(false);
+            let response = ui.selectable_label(is_selected, &row.label);
+
+            // Renaming a multibody joint would rename its body.
+            if response.double_clicked() && row.kind != RowKind::MultibodyJoint {
+                state.renaming = Some((row.entity, row.label.clone()));
+            } else if response.clicked() {
+                select(ui, selections, row.selectable);
+            }
+        }
+    }
+
+    if matches!(row.kind, RowKind::Body | RowKind::Collider) {
+        let is_visible = visibility
+            .get(row.entity)
+            .map(|v| v.1 != Visibility::Hidden)
+            .unwrap_or(true);
+        let visibility_icon = if is_visible { "🌑" } else { "🌕" };
+        if ui.button(visibility_icon).clicked() {
+            commands.entity(row.entity).insert(if is_visible {
+                Visibility::Hidden
+            } else {
+                Visibility::Visible
+            });
+
+            if is_visible {
+                // We hide the object, so it can’t remain selected.
+                if let Ok((_, mut selection)) = selections.get_mut(row.entity) {
+                    selection.selected = false;
+                }
+            }
+        }
+    }
+
+    if ui.button("❌").clicked() {
+        match row.kind {
+            RowKind::Body | RowKind::Collider => {
+                operations.push(Operation::Despawn(vec![row.entity]));
+            }
+            // The joint has its own entity, attached to the body it is listed under.
+            RowKind::ImpulseJoint if row.entity != row.selectable => {
+                operations.push(Operation::Despawn(vec![row.entity]));
+            }
+            // The joint components are removed from the physics context after the history
+            // checkpoint is saved.
+            RowKind::ImpulseJoint => {
+                history.checkpoint();
+                commands.entity(row.entity).remove::<ImpulseJoint>();
+            }
+            RowKind::MultibodyJoint => {
+                history.checkpoint();
+                commands.entity(row.entity).remove::<MultibodyJoint>();
+            }
+        }
+    }
+}
+
+/// Selects `entity`, or toggles its selection if Shift is pressed.
+fn select(ui: &egui::Ui, selections: &mut Query<(Entity, &mut Selection)>, entity: Entity) {
+    let toggle = ui.input(|i| i.modifiers.shift);
+
+    for (other, mut selection) in selections.iter_mut() {
+        if other == entity {
+            selection.selected = !(toggle && selection.selected);
+        } else if !toggle && selection.selected {
+            selection.selected = false;
+        }
+    }
+}
+
+/// Flattens the tree of bodies, colliders, and joints, keeping only the rows matching the
+/// filter and the children of expanded bodies.
+fn tree_rows(
+    state: &SceneExplorerState,
+    physics: &RapierContext,
+    bodies: &Query<RigidBodyComponentsMut>,
+    colliders: &Query<ColliderComponentsMut>,
+    names: &Query<&Name>,
+    impulse_joints: &Query<(Entity, &mut ImpulseJoint, Option<&Parent>)>,
+    multibody_joints: &Query<(Entity, &mut MultibodyJoint)>,
+) -> Vec<Row> {
+    let filter = state.filter.to_lowercase();
+    let label = |entity: Entity, default: &str| match names.get(entity) {
+        Ok(name) => name.to_string(),
+        Err(_) => format!("{} {:?}", default, entity),
+    };
+    let matches = |label: &str| filter.is_empty() || label.to_lowercase().contains(&filter);
+
+    // The joints are listed under the body they are attached to.
+    let mut joints: HashMap<Entity, Vec<(Entity, RowKind)>> = HashMap::new();
+    for (entity, _, parent) in impulse_joints.iter() {
+        let body = parent.map(|p| p.get()).unwrap_or(entity);
+        joints
+            .entry(body)
+            .or_default()
+            .push((entity, RowKind::ImpulseJoint));
+    }
+    for (entity, _) in multibody_joints.iter() {
+        joints
+            .entry(entity)
+            .or_default()
+            .push((entity, RowKind::MultibodyJoint));
+    }
+
+    let mut roots: Vec<(Entity, RowKind)> = bodies
+        .iter()
+        .map(|(entity, ..)| (entity, RowKind::Body))
+        .chain(
+            colliders
+                .iter()
+                .map(|(entity, ..)| entity)
+                .filter(|entity| {
+                    physics.collider_parent(*entity).is_none() && bodies.get(*entity).is_err()
+                })
+                .map(|entity| (entity, RowKind::Collider)),
+        )
+        .collect();
+    roots.sort_by_key(|(entity, _)| *entity);
+
+    let mut rows = vec![];
+
+    for (entity, kind) in roots {
+        let mut children = vec![];
+
+        if kind == RowKind::Body {
+            if let Some(rb) = physics
+                .entity2body()
+                .get(&entity)
+                .and_then(|h| physics.bodies.get(*h))
+            {
+                for handle in rb.colliders() {
+                    // The collider on the body entity itself is shown by the body row.
+                    if let Some(child) = physics
+                        .collider_entity(*handle)
+                        .filter(|child| *child != entity)
+                    {
+                        children.push(Row {
+                            entity: child,
+                            selectable: child,
+                            kind: RowKind::Collider,
+                            depth: 1,
+                            label: label(child, "Collider"),
+                            expanded: None,
+                        });
+                    }
+                }
+            }
+
+            for (joint, joint_kind) in joints.get(&entity).into_iter().flatten() {
+                // Multibody joints are components of the body entity, so they have no name.
+                let joint_label = if *joint_kind == RowKind::ImpulseJoint {
+                    label(*joint, "Impulse joint")
+                } else {
+                    "Multibody joint".to_string()
+                };
+                children.push(Row {
+                    entity: *joint,
+                    selectable: entity,
+                    kind: *joint_kind,
+                    depth: 1,
+                    label: joint_label,
+                    expanded: None,
+                });
+            }
+        }
+
+        let row_label = label(
+            entity,
+            if kind == RowKind::Body {
+                "Body"
+            } else {
+                "Collider"
+            },
+        );
+        if !filter.is_empty() {
+            children.retain(|child| matches(&child.label));
+        }
+        if !matches(&row_label) && children.is_empty() {
+            continue;
+        }
+
+        // Show the matching children while filtering.
+        let expanded = !filter.is_empty() || state.expanded.contains(&entity);
+        rows.push(Row {
+            entity,
+            selectable: entity,
+            kind,
+            depth: 0,
+            label: row_label,
+            expanded: (!children.is_empty()).then_some(expanded),
+        });
+
+        if expanded {
+            rows.extend(children);
+        }
+    }
+
+    rows
+}
@@ -0,0 +1,60 @@
+use crate::cli::CliArgs;
+use crate::operation::SimulationTimeline;
+use crate::PhysicsProgress;
+use bevy::window::Window;
+use bevy_egui::{egui, EguiContexts};
+use bevy_rapier::plugin::RapierConfiguration;
+
+use super::UiState;
+
+/// The scrubber for rewinding the simulation, displayed above the play/stop buttons.
+pub(super) fn ui(
+    window: &Window,
+    cli: &CliArgs,
+    ui_context: &mut EguiContexts,
+    ui_state: &mut UiState,
+    physics_config: &mut RapierConfiguration,
+    timeline: &mut SimulationTimeline,
+    progress: &PhysicsProgress,
+) {
+    if timeline.is_empty() {
+        return;
+    }
+
+    let width = 400.0;
+    let pos = [(window.width() - width) / 2.0, window.height() - 125.0];
+
+    egui::Window::new("timeline")
+        .resizable(false)
+        .title_bar(false)
+        .fixed_pos(pos)
+        .show(ui_context.ctx_mut(), |ui| {
+            ui.set_width(width);
+            ui.horizontal(|ui| {
+                let mut selected = timeline
+                    .index_at(progress.simulated_steps)
+                    .unwrap_or_default();
+                let last = timeline.len() - 1;
+
+                ui.spacing_mut().slider_width = width - 130.0;
+                let slider = ui.add(
+                    egui::Slider::new(&mut selected, 0..=last)
+                        .show_value(false)
+                        .trailing_fill(true),
+                );
+                ui.label(format!(
+                    "{:.2}s ({} steps)",
+                    progress.simulated_time, progress.simulated_steps
+                ));
+
+                if slider.changed() {
+                    // Pause so the scene stays at the selected time while scrubbing.
+                    if !cli.distributed_physics {
+                        physics_config.physics_pipeline_active = false;
+                    }
+                    ui_state.running = false;
+                    timeline.rewind(selected);
+                }
+            });
+        });
+}
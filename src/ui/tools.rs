@@ -1,5 +1,7 @@
+#[cfg(feature = "dim2")]
+use super::DrawShapeMode;
 use super::{ButtonTexture, SelectedTool, UiState};
-use crate::operation::{Operation, Operations};
+use crate::operation::{BooleanOp, JointKind, Operation, Operations};
 use bevy::window::Window;
 use bevy_egui::{egui, EguiContexts};
 
@@ -14,6 +16,8 @@ use {
 };
 
 #[cfg(feature = "dim3")]
+use super::mesh_import::MeshImportOptions;
+#[cfg(feature = "voxels")]
 use bevy_rapier::geometry::ComputedColliderShape;
 
 pub(super) fn ui(
@@ -74,7 +78,27 @@ pub(super) fn ui(
                     SelectedTool::Projectile,
                     ButtonTexture::Projectile.rich_text(),
                 );
+                ui.selectable_value(
+                    &mut ui_state.selected_tool,
+                    SelectedTool::AddJoint,
+                    ButtonTexture::AddJoint.rich_text(),
+                );
             });
+
+            if ui_state.selected_tool == SelectedTool::AddJoint {
+                ui.radio_value(&mut ui_state.joint_kind, JointKind::Fixed, "Fixed");
+                ui.radio_value(&mut ui_state.joint_kind, JointKind::Revolute, "Revolute");
+                ui.radio_value(&mut ui_state.joint_kind, JointKind::Prismatic, "Prismatic");
+                #[cfg(feature = "dim3")]
+                ui.radio_value(&mut ui_state.joint_kind, JointKind::Spherical, "Spherical");
+                ui.radio_value(&mut ui_state.joint_kind, JointKind::Rope, "Rope");
+                ui.radio_value(&mut ui_state.joint_kind, JointKind::Spring, "Spring");
+                ui.add_enabled(
+                    ui_state.joint_kind.supports_multibody(),
+                    egui::Checkbox::new(&mut ui_state.multibody_joint, "Multibody"),
+                )
+                .on_hover_text("Use a reduced-coordinates joint instead of an impulse joint.");
+            }
         });
 
     pos[0] += button_sz[0] * 4.0;
@@ -126,6 +150,25 @@ pub(super) fn ui(
                 );
             });
 
+            #[cfg(feature = "dim2")]
+            if ui_state.selected_tool == SelectedTool::DrawShape {
+                ui.radio_value(
+                    &mut ui_state.draw_shape_mode,
+                    DrawShapeMode::ConvexPolygon,
+                    "Convex",
+                );
+                ui.radio_value(
+                    &mut ui_state.draw_shape_mode,
+                    DrawShapeMode::ConvexDecomposition,
+                    "Concave",
+                );
+                ui.radio_value(
+                    &mut ui_state.draw_shape_mode,
+                    DrawShapeMode::Polyline,
+                    "Polyline",
+                );
+            }
+
             #[cfg(feature = "dim3")]
             #[cfg(not(target_arch = "wasm32"))]
             ui.horizontal(|ui| {
@@ -138,7 +181,7 @@ pub(super) fn ui(
                         .add_filter("OBJ Mesh", &["obj"])
                         .show_open_single_file()
                     {
-                        operations.push(Operation::ImportMesh(path, ComputedColliderShape::TriMesh))
+                        ui_state.mesh_import = Some(MeshImportOptions::new(path));
                     }
                 }
 
@@ -168,7 +211,7 @@ pub(super) fn ui(
                                         let voxels = Collider::voxels(&centers, voxel_size);
                                         operations.push(Operation::AddCollider(
                                             ColliderBundle::new(voxels),
-                                            RigidBodyBundle::fixed(),
+                                            Some(RigidBodyBundle::fixed()),
                                             Transform::default(),
                                         ))
                                     }
@@ -212,8 +255,25 @@ pub(super) fn ui(
                     ))
                     .clicked()
                 {
-                    operations.push(Operation::AddIntersection)
+                    operations.push(Operation::AddIntersection {
+                        op: ui_state.boolean_op,
+                        bake: ui_state.bake_boolean,
+                    })
                 }
             });
+            ui.radio_value(&mut ui_state.boolean_op, BooleanOp::Union, "Union");
+            ui.radio_value(
+                &mut ui_state.boolean_op,
+                BooleanOp::Intersection,
+                "Intersection",
+            );
+            ui.radio_value(
+                &mut ui_state.boolean_op,
+                BooleanOp::Difference,
+                "Difference",
+            )
+            .on_hover_text("Subtracts the second selected collider from the first selected one.");
+            ui.checkbox(&mut ui_state.bake_boolean, "Bake")
+                .on_hover_text("Replace the colliders by the result instead of previewing it.");
         });
 }
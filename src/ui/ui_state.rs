@@ -1,6 +1,14 @@
+use crate::operation::{BooleanOp, JointKind};
+use crate::utils::PhysicsMaterial;
 use bevy::prelude::*;
 use bevy_egui::egui::{Color32, FontId, RichText, TextureId};
 
+use super::array_generator::ArrayOptions;
+use super::material_inspector::MaterialPresets;
+#[cfg(feature = "dim3")]
+use super::mesh_import::MeshImportOptions;
+use super::scene_explorer::SceneExplorerState;
+
 // TODO: not sure where to put this?
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Resource)]
 pub enum ActiveMouseAction {
@@ -8,6 +16,7 @@ pub enum ActiveMouseAction {
     Selection,
     Drag,
     Projectile,
+    Cut,
     None,
 }
 
@@ -17,11 +26,27 @@ pub struct UiState {
     pub button_textures: Vec<TextureId>,
     pub debug_render_open: bool,
     pub simulation_infos_open: bool,
+    pub profiler_open: bool,
+    pub contacts_open: bool,
+    pub array_open: bool,
     pub selected_tool: SelectedTool,
     pub open_object_tab: OpenObjectTab,
     pub single_step: bool,
     pub running: bool,
     pub interpolation: bool,
+    #[cfg(feature = "dim3")]
+    pub mesh_import: Option<MeshImportOptions>,
+    #[cfg(feature = "dim2")]
+    pub draw_shape_mode: DrawShapeMode,
+    pub boolean_op: BooleanOp,
+    pub bake_boolean: bool,
+    pub joint_kind: JointKind,
+    pub multibody_joint: bool,
+    pub scene_explorer: SceneExplorerState,
+    pub array: ArrayOptions,
+    pub materials: MaterialPresets,
+    /// The material of the colliders created with the insertion tools.
+    pub new_body_material: PhysicsMaterial,
 }
 
 impl Default for UiState {
@@ -31,11 +56,26 @@ impl Default for UiState {
             button_textures: vec![],
             debug_render_open: false,
             simulation_infos_open: false,
+            profiler_open: false,
+            contacts_open: false,
+            array_open: false,
             selected_tool: SelectedTool::Drag,
             open_object_tab: OpenObjectTab::SelectionInspector,
             single_step: false,
             running: false,
             interpolation: true,
+            #[cfg(feature = "dim3")]
+            mesh_import: None,
+            #[cfg(feature = "dim2")]
+            draw_shape_mode: DrawShapeMode::ConvexPolygon,
+            boolean_op: BooleanOp::Intersection,
+            bake_boolean: false,
+            joint_kind: JointKind::Revolute,
+            multibody_joint: false,
+            scene_explorer: SceneExplorerState::default(),
+            array: ArrayOptions::default(),
+            materials: MaterialPresets::default(),
+            new_body_material: PhysicsMaterial::default(),
         }
     }
 }
@@ -47,6 +87,7 @@ pub enum SelectedTool {
     Rotate,
     Drag,
     Projectile,
+    AddJoint,
     AddBall,
     AddCuboid,
     AddCapsule,
@@ -59,6 +100,15 @@ pub enum SelectedTool {
     DrawShape,
 }
 
+/// The kind of collider created from a polygon drawn with the `DrawShape` tool.
+#[cfg(feature = "dim2")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DrawShapeMode {
+    ConvexPolygon,
+    ConvexDecomposition,
+    Polyline,
+}
+
 impl Default for SelectedTool {
     fn default() -> Self {
         SelectedTool::Translate
@@ -1,5 +1,7 @@
 pub use self::bevy_mesh_conversion::*;
+pub use self::polygon::*;
 pub use self::rigid_body_collider_bundles::*;
 
 mod bevy_mesh_conversion;
+mod polygon;
 mod rigid_body_collider_bundles;
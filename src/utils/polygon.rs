@@ -0,0 +1,197 @@
+use bevy::prelude::*;
+
+/// Twice the signed area of a simple polygon, positive if it is counterclockwise.
+pub fn polygon_signed_area(points: &[Vec2]) -> f32 {
+    (0..points.len())
+        .map(|i| points[i].perp_dot(points[(i + 1) % points.len()]))
+        .sum()
+}
+
+/// Checks if a simple polygon is convex.
+pub fn polygon_is_convex(points: &[Vec2]) -> bool {
+    let n = points.len();
+    let sign = polygon_signed_area(points).signum();
+    (0..n).all(|i| {
+        let a = points[i];
+        let b = points[(i + 1) % n];
+        let c = points[(i + 2) % n];
+        (b - a).perp_dot(c - b) * sign >= 0.0
+    })
+}
+
+/// Triangulates a simple polygon with the ear-clipping method.
+///
+/// Repeated and collinear consecutive vertices are skipped, since they don’t enclose any
+/// area. Returns `None` if the polygon intersects itself, or has no area.
+pub fn triangulate_polygon(points: &[Vec2]) -> Option<Vec<[u32; 3]>> {
+    let sign = polygon_signed_area(points).signum();
+    let mut remaining: Vec<u32> = (0..points.len() as u32)
+        .filter(|&k| points[k as usize] != points[(k as usize + 1) % points.len()])
+        .collect();
+    let mut triangles = vec![];
+
+    if polygon_intersects_itself(points, &remaining) {
+        return None;
+    }
+
+    while remaining.len() >= 3 {
+        let n = remaining.len();
+        let corner = |i: usize| {
+            let [ia, ib, ic] = [
+                remaining[(i + n - 1) % n],
+                remaining[i],
+                remaining[(i + 1) % n],
+            ];
+            let [a, b, c] = [ia, ib, ic].map(|k| points[k as usize]);
+            ([ia, ib, ic], (b - a).perp_dot(c - b) * sign)
+        };
+
+        // Clipping an ear can also leave a vertex aligned with its neighbors.
+        if let Some(flat) = (0..n).find(|&i| corner(i).1 == 0.0) {
+            remaining.remove(flat);
+            continue;
+        }
+
+        let ear = (0..n).find(|&i| {
+            let (ids, turn) = corner(i);
+            let [a, b, c] = ids.map(|k| points[k as usize]);
+
+            // The ear must be convex, and must not contain any other vertex.
+            turn > 0.0
+                && remaining
+                    .iter()
+                    .filter(|k| !ids.contains(k))
+                    .all(|k| !triangle_contains(a, b, c, points[*k as usize]))
+        })?;
+
+        triangles.push(corner(ear).0);
+        remaining.remove(ear);
+    }
+
+    (!triangles.is_empty()).then_some(triangles)
+}
+
+/// Checks if two edges of the polygon formed by the given vertices, that aren’t
+/// consecutive, touch each other.
+fn polygon_intersects_itself(points: &[Vec2], indices: &[u32]) -> bool {
+    let n = indices.len();
+    let edge = |i: usize| [indices[i], indices[(i + 1) % n]].map(|k| points[k as usize]);
+
+    (0..n).any(|i| {
+        (i + 2..n).filter(|&j| (j + 1) % n != i).any(|j| {
+            let [a1, a2] = edge(i);
+            let [b1, b2] = edge(j);
+            segments_intersect(a1, a2, b1, b2)
+        })
+    })
+}
+
+fn segments_intersect(a1: Vec2, a2: Vec2, b1: Vec2, b2: Vec2) -> bool {
+    let d1 = (a2 - a1).perp_dot(b1 - a1);
+    let d2 = (a2 - a1).perp_dot(b2 - a1);
+    let d3 = (b2 - b1).perp_dot(a1 - b1);
+    let d4 = (b2 - b1).perp_dot(a2 - b1);
+
+    if d1 * d2 < 0.0 && d3 * d4 < 0.0 {
+        return true;
+    }
+
+    // The segments may also touch at an endpoint, or overlap if they are collinear.
+    let on_segment =
+        |a: Vec2, b: Vec2, pt: Vec2| pt.cmpge(a.min(b)).all() && pt.cmple(a.max(b)).all();
+    (d1 == 0.0 && on_segment(a1, a2, b1))
+        || (d2 == 0.0 && on_segment(a1, a2, b2))
+        || (d3 == 0.0 && on_segment(b1, b2, a1))
+        || (d4 == 0.0 && on_segment(b1, b2, a2))
+}
+
+fn triangle_contains(a: Vec2, b: Vec2, c: Vec2, pt: Vec2) -> bool {
+    let d1 = (b - a).perp_dot(pt - a);
+    let d2 = (c - b).perp_dot(pt - b);
+    let d3 = (a - c).perp_dot(pt - c);
+    let has_neg = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_pos = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(has_neg && has_pos)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// The total area covered by the triangles.
+    fn triangles_area(points: &[Vec2], triangles: &[[u32; 3]]) -> f32 {
+        triangles
+            .iter()
+            .map(|t| polygon_signed_area(&t.map(|k| points[k as usize])).abs() / 2.0)
+            .sum()
+    }
+
+    fn check_triangulation(points: &[Vec2], expected_triangles: usize) {
+        let triangles = triangulate_polygon(points).unwrap();
+        assert_eq!(triangles.len(), expected_triangles);
+        let area = polygon_signed_area(points).abs() / 2.0;
+        assert!((triangles_area(points, &triangles) - area).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn triangulate_convex_polygon() {
+        let square = [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y];
+        check_triangulation(&square, 2);
+    }
+
+    #[test]
+    fn triangulate_concave_polygon() {
+        let l_shape = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(0.0, 2.0),
+        ];
+        check_triangulation(&l_shape, 4);
+
+        let mut clockwise = l_shape;
+        clockwise.reverse();
+        check_triangulation(&clockwise, 4);
+    }
+
+    #[test]
+    fn triangulate_polygon_with_collinear_vertices() {
+        let square = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(0.0, 2.0),
+            Vec2::new(0.0, 1.0),
+        ];
+        check_triangulation(&square, 2);
+    }
+
+    #[test]
+    fn self_intersecting_polygons_are_rejected() {
+        let bowtie = [Vec2::ZERO, Vec2::ONE, Vec2::X, Vec2::Y];
+        assert!(triangulate_polygon(&bowtie).is_none());
+
+        // Two squares touching at a single vertex.
+        let touching = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(0.0, 1.0),
+        ];
+        assert!(triangulate_polygon(&touching).is_none());
+    }
+
+    #[test]
+    fn flat_polygons_are_rejected() {
+        let segment = [Vec2::ZERO, Vec2::X, Vec2::X * 2.0];
+        assert!(triangulate_polygon(&segment).is_none());
+    }
+}
@@ -2,7 +2,7 @@ use crate::render::{ColliderOutlineRender, ColliderRender};
 use crate::styling::ColorGenerator;
 use bevy::prelude::*;
 use bevy_rapier::prelude::*;
-use bevy_rapier::rapier::dynamics::RigidBody as RapierRigidBody;
+use bevy_rapier::rapier::dynamics::{RigidBody as RapierRigidBody, RigidBodyAdditionalMassProps};
 use bevy_rapier::rapier::geometry::Collider as RapierCollider;
 
 pub type RigidBodyComponentsMut<'a> = (
@@ -27,6 +27,9 @@ pub type ColliderComponentsMut<'a> = (
     Option<&'a mut ColliderMassProperties>,
     Option<&'a mut CollisionGroups>,
     Option<&'a ColliderDisabled>,
+    Option<&'a mut Friction>,
+    Option<&'a mut Restitution>,
+    Option<&'a mut ContactSkin>,
 );
 
 #[derive(Clone, Bundle, Default)]
@@ -34,24 +37,133 @@ pub struct ColliderBundle {
     pub collider: Collider,
     pub mass_properties: ColliderMassProperties,
     pub collision_groups: CollisionGroups,
+    pub solver_groups: SolverGroups,
+    pub friction: Friction,
+    pub restitution: Restitution,
+    pub active_events: ActiveEvents,
+    pub active_collision_types: ActiveCollisionTypes,
+    pub active_hooks: ActiveHooks,
+    pub contact_force_event_threshold: ContactForceEventThreshold,
+    pub contact_skin: ContactSkin,
 }
 
 impl ColliderBundle {
     pub fn new(collider: Collider) -> Self {
         Self {
             collider,
-            mass_properties: Default::default(),
-            collision_groups: Default::default(),
+            ..Default::default()
         }
     }
+
+    pub fn with_material(mut self, material: &PhysicsMaterial) -> Self {
+        self.friction = material.friction();
+        self.restitution = material.restitution();
+        self.contact_skin = ContactSkin(material.contact_skin);
+        self
+    }
+}
+
+/// The surface properties of a collider: its `Friction`, `Restitution`, and `ContactSkin`.
+#[derive(Copy, Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PhysicsMaterial {
+    pub friction: Real,
+    pub friction_combine_rule: CoefficientCombineRule,
+    pub restitution: Real,
+    pub restitution_combine_rule: CoefficientCombineRule,
+    pub contact_skin: Real,
+}
+
+impl Default for PhysicsMaterial {
+    fn default() -> Self {
+        Self::from_components(None, None, None)
+    }
 }
 
+impl PhysicsMaterial {
+    pub const fn new(friction: Real, restitution: Real) -> Self {
+        Self {
+            friction,
+            friction_combine_rule: CoefficientCombineRule::Average,
+            restitution,
+            restitution_combine_rule: CoefficientCombineRule::Average,
+            contact_skin: 0.0,
+        }
+    }
+
+    /// The material of a collider, with the default value of each missing component.
+    pub fn from_components(
+        friction: Option<&Friction>,
+        restitution: Option<&Restitution>,
+        contact_skin: Option<&ContactSkin>,
+    ) -> Self {
+        let friction = friction.copied().unwrap_or_default();
+        let restitution = restitution.copied().unwrap_or_default();
+        Self {
+            friction: friction.coefficient,
+            friction_combine_rule: friction.combine_rule,
+            restitution: restitution.coefficient,
+            restitution_combine_rule: restitution.combine_rule,
+            contact_skin: contact_skin.map(|skin| skin.0).unwrap_or_default(),
+        }
+    }
+
+    pub fn friction(&self) -> Friction {
+        Friction {
+            coefficient: self.friction,
+            combine_rule: self.friction_combine_rule,
+        }
+    }
+
+    pub fn restitution(&self) -> Restitution {
+        Restitution {
+            coefficient: self.restitution,
+            combine_rule: self.restitution_combine_rule,
+        }
+    }
+}
+
+/// The conversion doesn’t cover the `Sensor` and `ColliderDisabled` marker components:
+/// they need to be inserted separately based on `RapierCollider::is_sensor` and
+/// `RapierCollider::is_enabled`.
 impl<'a> From<&'a RapierCollider> for ColliderBundle {
     fn from(value: &'a RapierCollider) -> Self {
+        // Prefer keeping the density so the mass stays consistent if the shape is rescaled.
+        let density = value.density();
+        let mprops = value.mass_properties();
+        let mass_properties = if value.shape().mass_properties(density) == mprops {
+            ColliderMassProperties::Density(density)
+        } else {
+            ColliderMassProperties::MassProperties(MassProperties::from_rapier(mprops))
+        };
+
         Self {
             collider: Collider::from(value.shared_shape().clone()),
-            mass_properties: Default::default(),  // FIXME
-            collision_groups: Default::default(), // FIXME
+            mass_properties,
+            collision_groups: CollisionGroups::new(
+                Group::from_bits_truncate(value.collision_groups().memberships.bits()),
+                Group::from_bits_truncate(value.collision_groups().filter.bits()),
+            ),
+            solver_groups: SolverGroups::new(
+                Group::from_bits_truncate(value.solver_groups().memberships.bits()),
+                Group::from_bits_truncate(value.solver_groups().filter.bits()),
+            ),
+            friction: Friction {
+                coefficient: value.friction(),
+                combine_rule: value.friction_combine_rule(),
+            },
+            restitution: Restitution {
+                coefficient: value.restitution(),
+                combine_rule: value.restitution_combine_rule(),
+            },
+            active_events: ActiveEvents::from_bits_truncate(value.active_events().bits()),
+            active_collision_types: ActiveCollisionTypes::from_bits_truncate(
+                value.active_collision_types().bits(),
+            ),
+            active_hooks: ActiveHooks::from_bits_truncate(value.active_hooks().bits()),
+            contact_force_event_threshold: ContactForceEventThreshold(
+                value.contact_force_event_threshold(),
+            ),
+            contact_skin: ContactSkin(value.contact_skin()),
         }
     }
 }
@@ -79,11 +191,14 @@ impl Clone for ColliderRenderBundle {
 
 impl ColliderRenderBundle {
     pub fn new(colors: &mut ColorGenerator) -> Self {
-        let color = colors.gen_color();
+        Self::with_color(colors.gen_color())
+    }
+
+    pub fn with_color(color: Color) -> Self {
         let outline_color = ColorGenerator::outline_color(color);
         Self {
             render: ColliderRender::from(color),
-            render_outline: ColliderOutlineRender::new(outline_color, 0.02),
+            render_outline: ColliderOutlineRender::new(outline_color, 1.0),
             ..Default::default()
         }
     }
@@ -102,6 +217,8 @@ pub struct RigidBodyBundle {
     pub dominance: Dominance,
     pub sleeping: Sleeping,
     pub damping: Damping,
+    pub soft_ccd: SoftCcd,
+    pub additional_solver_iterations: AdditionalSolverIterations,
 }
 
 impl RigidBodyBundle {
@@ -134,8 +251,22 @@ impl RigidBodyBundle {
     }
 }
 
+/// The conversion doesn’t cover the `RigidBodyDisabled` marker component: it needs to
+/// be inserted separately based on `RapierRigidBody::is_enabled`.
 impl<'a> From<&'a RapierRigidBody> for RigidBodyBundle {
     fn from(value: &'a RapierRigidBody) -> Self {
+        let additional_mass_properties =
+            match value.mass_properties().additional_local_mprops.as_deref() {
+                Some(RigidBodyAdditionalMassProps::Mass(mass)) => {
+                    AdditionalMassProperties::Mass(*mass)
+                }
+                Some(RigidBodyAdditionalMassProps::MassProps(mprops)) => {
+                    AdditionalMassProperties::MassProperties(MassProperties::from_rapier(*mprops))
+                }
+                None => AdditionalMassProperties::default(),
+            };
+        let activation = value.activation();
+
         Self {
             rigid_body: value.body_type().into(),
             velocity: Velocity {
@@ -145,16 +276,37 @@ impl<'a> From<&'a RapierRigidBody> for RigidBodyBundle {
                 #[cfg(feature = "dim3")]
                 angvel: (*value.angvel()).into(),
             },
-            // additional_mass_properties: AdditionalMassProperties,
-            // mass_properties: ReadMassProperties,
-            // locked_axes: LockedAxes,
-            // forces: ExternalForce,
-            // gravity_scale: GravityScale,
-            // ccd: Ccd,
-            // dominance: Dominance,
-            // sleeping: Sleeping,
-            // damping: Damping,
-            ..Default::default()
+            additional_mass_properties,
+            // This is a read-only component, it will be filled by bevy_rapier.
+            mass_properties: ReadMassProperties::default(),
+            locked_axes: LockedAxes::from_bits_truncate(value.locked_axes().bits()),
+            forces: ExternalForce {
+                force: value.user_force().into(),
+                #[cfg(feature = "dim2")]
+                torque: value.user_torque(),
+                #[cfg(feature = "dim3")]
+                torque: value.user_torque().into(),
+            },
+            gravity_scale: GravityScale(value.gravity_scale()),
+            ccd: Ccd {
+                enabled: value.is_ccd_enabled(),
+            },
+            dominance: Dominance::group(value.dominance_group()),
+            sleeping: Sleeping {
+                normalized_linear_threshold: activation.normalized_linear_threshold,
+                angular_threshold: activation.angular_threshold,
+                sleeping: activation.sleeping,
+            },
+            damping: Damping {
+                linear_damping: value.linear_damping(),
+                angular_damping: value.angular_damping(),
+            },
+            soft_ccd: SoftCcd {
+                prediction: value.soft_ccd_prediction(),
+            },
+            additional_solver_iterations: AdditionalSolverIterations(
+                value.additional_solver_iterations(),
+            ),
         }
     }
 }